@@ -0,0 +1,99 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, DeriveInput, ItemFn, LitStr, FnArg, ReturnType, Type};
+
+/// Register ```IBaseService``` type on ```Application::register_auto_services```
+#[proc_macro_attribute]
+pub fn service(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    auto_registration(item, quote!(service))
+}
+
+/// Register ```IBackgroundService``` type wrapped in ```BackgroundService``` on ```Application::register_auto_services```
+#[proc_macro_attribute]
+pub fn background_service(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    auto_registration(item, quote!(background_service))
+}
+
+/// Register ```IStartup``` type on ```Application::register_auto_services```
+#[proc_macro_attribute]
+pub fn startup(_attr: TokenStream, item: TokenStream) -> TokenStream {
+    auto_registration(item, quote!(startup))
+}
+
+fn auto_registration(item: TokenStream, registration: TokenStream2) -> TokenStream {
+    let input = parse_macro_input!(item as DeriveInput);
+    let ident = &input.ident;
+
+    if !input.generics.params.is_empty() {
+        return syn::Error::new_spanned(&input.generics, "auto registered type can't be generic").to_compile_error().into();
+    }
+
+    quote! {
+        #input
+
+        ::anthill_service_system::inventory::submit! {
+            ::anthill_service_system::registration::AutoRegistration::#registration::<#ident>()
+        }
+    }.into()
+}
+
+/// Generate synchronous ```main```, which creates runtime from ```CoreConfig::runtime```, registers auto registered types, runs annotated function and then application.
+/// Annotated function is ```async fn main()``` or ```async fn main(app: &mut Application)``` without return value.
+/// Configuration path set with ```#[main(config = "path")]```, default ```app_config.json```
+#[proc_macro_attribute]
+pub fn main(attr: TokenStream, item: TokenStream) -> TokenStream {
+    let mut config_path = LitStr::new("app_config.json", proc_macro2::Span::call_site());
+
+    let attr_parser = syn::meta::parser(|meta| {
+        if meta.path.is_ident("config") {
+            config_path = meta.value()?.parse()?;
+            Ok(())
+        } else {
+            Err(meta.error("unsupported main attribute, expected config"))
+        }
+    });
+    parse_macro_input!(attr with attr_parser);
+
+    let mut configure = parse_macro_input!(item as ItemFn);
+
+    if configure.sig.asyncness.is_none() {
+        return syn::Error::new_spanned(configure.sig.fn_token, "main function must be async").to_compile_error().into();
+    }
+
+    // result of configure can't be returned from generated main
+    if let ReturnType::Type(_, output) = &configure.sig.output {
+        if !matches!(output.as_ref(), Type::Tuple(tuple) if tuple.elems.is_empty()) {
+            return syn::Error::new_spanned(output, "main function must return ()").to_compile_error().into();
+        }
+    }
+
+    let configure_call = match configure.sig.inputs.len() {
+        0 => quote!(__anthill_configure().await),
+        1 if matches!(configure.sig.inputs.first(), Some(FnArg::Typed(_))) => quote!(__anthill_configure(app).await),
+        _ => return syn::Error::new_spanned(&configure.sig.inputs, "main function accepts only application argument").to_compile_error().into(),
+    };
+
+    configure.sig.ident = syn::Ident::new("__anthill_configure", configure.sig.ident.span());
+
+    // visibility moved to generated main
+    let vis = std::mem::replace(&mut configure.vis, syn::Visibility::Inherited);
+
+    quote! {
+        #vis fn main() -> ::std::result::Result<(), ::anthill_service_system::types::RunBlockingError> {
+            #configure
+
+            fn __anthill_configure_boxed<'a>(app: &'a mut ::anthill_service_system::Application) -> ::anthill_service_system::registration::ConfigureApplicationFuture<'a> {
+                ::std::boxed::Box::pin(async move {
+                    let _ = &app;
+                    #configure_call;
+                })
+            }
+
+            ::anthill_service_system::Application::run_auto_blocking(
+                ::anthill_service_system::configs::ConfigurationSource::json_file(#config_path),
+                __anthill_configure_boxed,
+            )
+        }
+    }.into()
+}
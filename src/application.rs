@@ -1,27 +1,56 @@
-use anthill_di_configuration_extension::{extensions::{RegisterSourceExtension}, source::JsonFileConfiguration, ConfigurationSnapshot};
-use tokio::time::timeout;
+use anthill_di_configuration_extension::extensions::RegisterSourceExtension;
 use std::sync::{Arc, Weak};
-use tokio::sync::RwLock;
-use std::any::{type_name, TypeId};
+use serde::{Deserialize, Serialize};
+use tokio::{runtime::{Builder, Runtime}, sync::RwLock};
+use anthill_di_configuration_extension::source::ISource;
+use std::{any::{type_name, TypeId}, time::Duration};
 
 use crate::{
-    configs::CoreConfig,
+    configs::{CoreConfig, CoreConfigSnapshot, ConfigurationSource, ConfigSnapshot, ProcessConfig},
     types::{
         AddServiceError,
         AddServiceResult,
         AppRunResult,
-        AppRunError, AddStartupError, AddStartupResult, AddLifeTimeManagerResult, AddLifeTimeManagerError
+        AppRunError, PidFileError, AddStartupError, AddStartupResult, AddLifeTimeManagerResult, AddLifeTimeManagerError,
+        ServiceManagerError,
+        AddHookError,
+        AddHookResult,
+        AddConfigurationError,
+        AddConfigurationResult,
+        ReloadConfigurationError,
+        ReloadConfigurationResult,
+        RunBlockingError,
+        RunBlockingResult,
+        HookError,
     },
-    services::IBaseService,
+    hooks::{IPreStartHook, IPostStopHook},
+    time::Clock,
+    metrics::{Metrics, MetricsRegistry, WAIT_FOR_STOP_DURATION},
+    events::{HostEvent, HostEventBus, StopReason},
+    services::{IBaseService, ServiceManager, ServiceScope, ServiceScopeFactory, ProcessService, ServiceId, Supervisor, SupervisorBuilder, HeartbeatRegistry, StallWatchdog, TaskTracker, TaskSpawner, CompletionTracker, CompletionMode, CompletionReport},
     IStartup,
+    PidFile,
     life_time::{
         ILifeTimeManager,
-        CtrlCLifeTimeManager
+        CtrlCLifeTimeManager,
+        CompositeLifeTimeManager,
+        CompositeLifeTimeManagerBuilder,
+        ShutdownSignal,
+        ShutdownPhase,
     },
 };
 
+#[cfg(feature = "macros")]
+use crate::{
+    registration::{AutoRegistration, ConfigureApplicationFuture},
+    types::AutoRegistrationResult,
+};
+
+#[cfg(unix)]
+use crate::{SystemdNotifier, SystemdNotifyTasks, ActivatedSockets, ActivatedTcpListener, ActivatedUnixListener, IActivatedSocketName};
+
 use anthill_di::{
-    types::{BuildDependencyResult, BuildDependencyError},
+    types::{BuildDependencyResult, BuildDependencyError, AddDependencyError, AddDependencyResult, TypeInfo},
     DependencyContext,
     DependencyLifeCycle,
     Constructor
@@ -30,112 +59,344 @@ use anthill_di::{
 
 pub struct Application {
     pub root_ioc_context: DependencyContext,
-    pub core_config: Arc<RwLock<ConfigurationSnapshot<CoreConfig, JsonFileConfiguration::<CoreConfig>>>>,
+    pub core_config: Arc<RwLock<CoreConfigSnapshot>>,
+    pub service_manager: Arc<ServiceManager>,
+    /// Replace with ```Clock::set_clock``` to drive host timers manually
+    pub clock: Arc<Clock>,
+    /// Subscribe for typed host events, also resolvable as ```Arc<HostEventBus>```
+    pub event_bus: Arc<HostEventBus>,
+    pid_file: Option<PidFile>,
+    #[cfg(unix)]
+    systemd_notifier: Option<Arc<SystemdNotifier>>,
+    #[cfg(unix)]
+    systemd_notify_tasks: Option<SystemdNotifyTasks>,
 }
 
 #[async_trait_with_sync::async_trait(Sync)]
 impl Constructor for Application {
     async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
-        log::info!("Application creating ...");
+        host_event!(info, "Application creating ...");
 
         let mut ctx = ctx;
         ctx.set_empty_scope();
 
-        ctx.register_source(|_| Ok(JsonFileConfiguration::<CoreConfig>::new("app_config.json".to_string(), true))).await
-            .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
-        ctx.register_type::<RwLock<ConfigurationSnapshot<CoreConfig, JsonFileConfiguration::<CoreConfig>>>>(DependencyLifeCycle::Singleton).await
-            .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
-
-        let core_config = ctx.resolve().await.unwrap();
-
-        log::info!("Application created");
+        // Error only if source already selected before application creation
+        _ = ctx.register_source(|_| Ok(ConfigurationSource::<CoreConfig>::json_file("app_config.json"))).await;
 
-        Ok(Self {
-            root_ioc_context: ctx,
-            core_config: core_config,
-        })
+        Self::build(ctx).await
     }
 }
 
 impl Application {
     pub async fn new(configuration_path: Option<String>) -> BuildDependencyResult<Self> {
-        log::info!("Application creating ...");
-        let mut root_ioc_context = DependencyContext::new_root();
-
         let configuration_path = configuration_path.unwrap_or("app_config.json".to_string());
+        Self::with_configuration_source(ConfigurationSource::json_file(&configuration_path)).await
+    }
 
-        root_ioc_context.register_source(move |_| Ok(JsonFileConfiguration::<CoreConfig>::new(configuration_path.clone(), true))).await
-            .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
-        root_ioc_context.register_type::<RwLock<ConfigurationSnapshot<CoreConfig, JsonFileConfiguration::<CoreConfig>>>>(DependencyLifeCycle::Singleton).await
+    /// Create application with ```CoreConfig``` loaded from source, use ```ConfigurationSource::in_memory``` to avoid disk I/O
+    pub async fn with_configuration_source(configuration_source: ConfigurationSource<CoreConfig>) -> BuildDependencyResult<Self> {
+        host_event!(info, "Application creating ...");
+        let mut root_ioc_context = DependencyContext::new_root();
+
+        root_ioc_context.register_source(move |_| Ok(configuration_source.clone())).await
             .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
 
-        let core_config = root_ioc_context.resolve().await.unwrap();
+        Self::build(root_ioc_context).await
+    }
+
+    /// Register host services in context with registered ```CoreConfig``` source
+    async fn build(root_ioc_context: DependencyContext) -> BuildDependencyResult<Self> {
+        register_host_services(&root_ioc_context).await?;
 
-        log::info!("Application created");
+        let service_manager: Arc<ServiceManager> = root_ioc_context.resolve().await?;
+        service_manager.set_self_ref();
+
+        host_event!(info, "Application created");
 
         Ok(Self {
+            core_config: root_ioc_context.resolve().await?,
+            service_manager,
+            clock: root_ioc_context.resolve().await?,
+            event_bus: root_ioc_context.resolve().await?,
             root_ioc_context,
-            core_config: core_config,
+            pid_file: None,
+            #[cfg(unix)]
+            systemd_notifier: None,
+            #[cfg(unix)]
+            systemd_notify_tasks: None,
+        })
+    }
+
+    /// Create runtime from ```CoreConfig::runtime``` of configuration source, then create application, register startup and run it.
+    /// Synchronous entry point, must not be called inside async context
+    pub fn run_blocking<TStartup: IStartup + Constructor>(configuration_source: ConfigurationSource<CoreConfig>) -> RunBlockingResult {
+        #[cfg(unix)]
+        let activated_sockets = ActivatedSockets::from_env();
+
+        let runtime = build_runtime(&configuration_source)?;
+
+        runtime.block_on(async move {
+            let mut app = Self::with_configuration_source(configuration_source).await
+                .map_err(RunBlockingError::IocBuildDependencyError)?;
+
+            #[cfg(unix)]
+            app.set_activated_sockets(activated_sockets).await.map_err(RunBlockingError::IocBuildDependencyError)?;
+
+            app.register_startup::<TStartup>().await.map_err(RunBlockingError::AddStartupError)?;
+
+            app.run().await.map_err(|e| RunBlockingError::AppRunError(Box::new(e)))
+        })
+    }
+
+    /// Like ```run_blocking```, but registers types collected by ```#[service]```, ```#[background_service]``` and ```#[startup]```, then calls configure. Used by ```#[main]```
+    #[cfg(feature = "macros")]
+    pub fn run_auto_blocking(configuration_source: ConfigurationSource<CoreConfig>, configure: for<'a> fn(&'a mut Application) -> ConfigureApplicationFuture<'a>) -> RunBlockingResult {
+        #[cfg(unix)]
+        let activated_sockets = ActivatedSockets::from_env();
+
+        let runtime = build_runtime(&configuration_source)?;
+
+        runtime.block_on(async move {
+            let mut app = Self::with_configuration_source(configuration_source).await
+                .map_err(RunBlockingError::IocBuildDependencyError)?;
+
+            #[cfg(unix)]
+            app.set_activated_sockets(activated_sockets).await.map_err(RunBlockingError::IocBuildDependencyError)?;
+
+            app.register_auto_services().await.map_err(RunBlockingError::AutoRegistrationError)?;
+            configure(&mut app).await;
+
+            app.run().await.map_err(|e| RunBlockingError::AppRunError(Box::new(e)))
         })
     }
 
+    /// Register all types annotated with ```#[service]```, ```#[background_service]``` and ```#[startup]``` linked into binary
+    #[cfg(feature = "macros")]
+    pub async fn register_auto_services(&mut self) -> AutoRegistrationResult {
+        for registration in AutoRegistration::iter() {
+            (registration.register)(self).await?;
+            host_event!(info, "Auto registration applied", type_name = (registration.type_name)());
+        }
+
+        Ok(())
+    }
+
     pub async fn register_service<TBaseService: IBaseService + Constructor>(&mut self) -> AddServiceResult {
-        log::info!("Starting registration service, name:[{service_name}] type_id:[{type_id:?}]", service_name = type_name::<TBaseService>(), type_id = TypeId::of::<TBaseService>());
+        host_event!(info, "Starting registration service ...", service = type_name::<TBaseService>(), type_id = TypeId::of::<TBaseService>());
 
         self.root_ioc_context.register_type::<RwLock<TBaseService>>(DependencyLifeCycle::Singleton).await
-            .map_err(|e| AddServiceError::IocAddDependencyError(e))?
-            .map_as::<RwLock<dyn IBaseService>>().await.map_err(|e| AddServiceError::IocMapComponentError(e))?;
+            .map_err(AddServiceError::IocAddDependencyError)?
+            .map_as::<RwLock<dyn IBaseService>>().await.map_err(AddServiceError::IocMapComponentError)?;
+
+        host_event!(info, "Service registered", service = type_name::<TBaseService>(), type_id = TypeId::of::<TBaseService>());
+
+        Ok(())
+    }
+
+    /// Supervisor children are built from ioc on application start
+    pub async fn register_supervisor(&mut self, supervisor: SupervisorBuilder) -> ServiceId {
+        host_event!(info, "Supervisor registered", supervisor = supervisor.get_name());
+
+        self.service_manager.add_service_instance(Supervisor::new(supervisor, Arc::downgrade(&self.service_manager), true)).await
+    }
+
+    /// Check heartbeats from ```HeartbeatRegistry``` every ```check_interval```, see ```StallWatchdog```
+    pub async fn register_stall_watchdog(&mut self, check_interval: Duration) -> ServiceId {
+        host_event!(info, "Stall watchdog registered", check_interval = check_interval);
 
-        log::info!("Service registered, name:[{service_name}] type_id:[{type_id:?}]", service_name = type_name::<TBaseService>(), type_id = TypeId::of::<TBaseService>());
+        self.service_manager.add_service_instance(StallWatchdog::new(check_interval, Arc::downgrade(&self.service_manager))).await
+    }
+
+    /// Run external command as service, see ```ProcessService```
+    pub async fn register_process(&mut self, config: ProcessConfig) -> ServiceId {
+        host_event!(info, "Process registered", process = config.get_name());
+
+        self.service_manager.add_service_instance(ProcessService::new(config, &self.service_manager)).await
+    }
+
+    pub async fn register_pre_start_hook<THook: IPreStartHook + Constructor>(&mut self) -> AddHookResult {
+        // Hook type may be already registered (as post stop hook or service), then map existing singleton
+        let map_result = match self.root_ioc_context.register_type::<RwLock<THook>>(DependencyLifeCycle::Singleton).await {
+            Ok(builder) => builder.map_as::<RwLock<dyn IPreStartHook>>().await.map(|_| ()),
+            Err(AddDependencyError::DependencyExist { .. }) => self.root_ioc_context.map_component::<RwLock<THook>, RwLock<dyn IPreStartHook>>().await.map(|_| ()),
+        };
+
+        map_result.map_err(AddHookError::IocMapComponentError)?;
+
+        host_event!(info, "Pre start hook registered", hook = type_name::<THook>());
+
+        Ok(())
+    }
+
+    pub async fn register_post_stop_hook<THook: IPostStopHook + Constructor>(&mut self) -> AddHookResult {
+        // Hook type may be already registered (as pre start hook or service), then map existing singleton
+        let map_result = match self.root_ioc_context.register_type::<RwLock<THook>>(DependencyLifeCycle::Singleton).await {
+            Ok(builder) => builder.map_as::<RwLock<dyn IPostStopHook>>().await.map(|_| ()),
+            Err(AddDependencyError::DependencyExist { .. }) => self.root_ioc_context.map_component::<RwLock<THook>, RwLock<dyn IPostStopHook>>().await.map(|_| ()),
+        };
+
+        map_result.map_err(AddHookError::IocMapComponentError)?;
+
+        host_event!(info, "Post stop hook registered", hook = type_name::<THook>());
 
         Ok(())
     }
 
     pub async fn register_startup<TStartup: IStartup + Constructor>(&mut self) -> AddStartupResult {
         self.root_ioc_context.register_type::<RwLock<TStartup>>(DependencyLifeCycle::Scoped).await
-            .map_err(|e| AddStartupError::IocAddDependencyError(e))?
+            .map_err(AddStartupError::IocAddDependencyError)?
             .map_as::<RwLock<dyn IStartup>>().await
-            .map_err(|e| AddStartupError::IocMapComponentError(e))?;
+            .map_err(AddStartupError::IocMapComponentError)?;
 
         Ok(())
     }
 
     pub async fn register_life_time_manager<TLifeTimeManager: ILifeTimeManager + Constructor>(&mut self) -> AddLifeTimeManagerResult {
         self.root_ioc_context.register_type::<TLifeTimeManager>(DependencyLifeCycle::Singleton).await
-            .map_err(|e| AddLifeTimeManagerError::IocAddDependencyError(e))?
+            .map_err(AddLifeTimeManagerError::IocAddDependencyError)?
+            .map_as::<dyn ILifeTimeManager>().await
+            .map_err(AddLifeTimeManagerError::IocMapComponentError)?;
+        Ok(())
+    }
+
+    /// Register user configuration loaded from source, resolve as ```Arc<RwLock<ConfigSnapshot<TConfiguration>>>```
+    pub async fn register_configuration<TConfiguration>(&mut self, configuration_source: ConfigurationSource<TConfiguration>) -> AddConfigurationResult
+    where
+        for<'de> TConfiguration: Deserialize<'de> + Serialize + Default + Clone + Sync + Send + 'static
+    {
+        self.root_ioc_context.register_source(move |_| Ok(configuration_source.clone())).await
+            .map_err(AddConfigurationError::IocAddDependencyError)?;
+        self.root_ioc_context.register_type::<RwLock<ConfigSnapshot<TConfiguration>>>(DependencyLifeCycle::Singleton).await
+            .map_err(AddConfigurationError::IocAddDependencyError)?;
+        Ok(())
+    }
+
+    /// Reload registered configuration (or ```CoreConfig```) from its source and publish ```HostEvent::ConfigurationReloaded```
+    pub async fn reload_configuration<TConfiguration>(&self) -> ReloadConfigurationResult
+    where
+        for<'de> TConfiguration: Deserialize<'de> + Serialize + Default + Clone + Sync + Send + 'static
+    {
+        let snapshot = self.root_ioc_context.resolve::<Arc<RwLock<ConfigSnapshot<TConfiguration>>>>().await
+            .map_err(ReloadConfigurationError::IocBuildDependencyError)?;
+
+        snapshot.write().await.sync().await.map_err(ReloadConfigurationError::LoadConfigurationError)?;
+
+        let configuration_type_info = TypeInfo::from_type::<TConfiguration>();
+        host_event!(info, "Configuration reloaded", configuration = configuration_type_info.type_name);
+        self.event_bus.publish(HostEvent::ConfigurationReloaded { configuration_type_info });
+
+        Ok(())
+    }
+
+    /// Register already created life time manager, like ```DeadlineLifeTimeManager``` or ```IdleLifeTimeManager```
+    pub async fn register_life_time_manager_instance<TLifeTimeManager: ILifeTimeManager + 'static>(&mut self, life_time_manager: TLifeTimeManager) -> AddLifeTimeManagerResult {
+        self.root_ioc_context.register_instance(life_time_manager).await
+            .map_err(AddLifeTimeManagerError::IocAddDependencyError)?
+            .map_as::<dyn ILifeTimeManager>().await
+            .map_err(AddLifeTimeManagerError::IocMapComponentError)?;
+        Ok(())
+    }
+
+    /// Run until all tracked background services complete (or life time manager stops application), return execute results
+    pub async fn run_to_completion(&mut self, mode: CompletionMode) -> Result<CompletionReport, AppRunError> {
+        let completion_tracker = self.root_ioc_context.resolve::<Arc<CompletionTracker>>().await
+            .map_err(AppRunError::IocBuildDependencyError)?;
+
+        completion_tracker.set_mode(mode);
+
+        self.run().await?;
+
+        Ok(completion_tracker.get_report())
+    }
+
+    /// Register composite life time manager, which stops application when first child stops
+    pub async fn register_composite_life_time_manager(&mut self, builder: CompositeLifeTimeManagerBuilder) -> AddLifeTimeManagerResult {
+        self.root_ioc_context.register_async_closure(move |ctx| CompositeLifeTimeManager::build(builder.clone(), ctx), DependencyLifeCycle::Singleton).await
+            .map_err(AddLifeTimeManagerError::IocAddDependencyError)?
             .map_as::<dyn ILifeTimeManager>().await
-            .map_err(|e| AddLifeTimeManagerError::IocMapComponentError(e))?;
+            .map_err(AddLifeTimeManagerError::IocMapComponentError)?;
+        Ok(())
+    }
+
+    /// Make sockets from ```ActivatedSockets::from_env``` resolvable as ```Arc<ActivatedSockets>```, ```run_blocking``` sets them by default
+    #[cfg(unix)]
+    pub async fn set_activated_sockets(&mut self, activated_sockets: ActivatedSockets) -> BuildDependencyResult<()> {
+        self.root_ioc_context.resolve::<Arc<ActivatedSockets>>().await?.append(activated_sockets);
+        Ok(())
+    }
+
+    /// Register activated socket by name, resolve it as ```ActivatedTcpListener<TName>``` or ```ActivatedUnixListener<TName>```
+    #[cfg(unix)]
+    pub async fn register_activated_socket<TName: IActivatedSocketName>(&mut self) -> AddDependencyResult<()> {
+        self.root_ioc_context.register_type::<ActivatedTcpListener<TName>>(DependencyLifeCycle::Transient).await?;
+        self.root_ioc_context.register_type::<ActivatedUnixListener<TName>>(DependencyLifeCycle::Transient).await?;
         Ok(())
     }
 
+    /// Use notifier instead of ```SystemdNotifier::from_env```, which is used by default
+    #[cfg(unix)]
+    pub fn set_systemd_notifier(&mut self, notifier: SystemdNotifier) {
+        self.systemd_notifier = Some(Arc::new(notifier));
+    }
+
     pub async fn run(&mut self) -> AppRunResult {
-        self.apply_life_time_manager().await?;
         self.apply_startups().await?;
+        self.apply_life_time_manager().await?;
+        self.apply_pid_file().await?;
+
+        #[cfg(unix)]
+        self.apply_systemd_notify();
 
-        log::info!("Resolving services ...");
-        let mut services = self.root_ioc_context.resolve_collection::<Arc<RwLock<dyn IBaseService>>>().await
-            .map_err(|e| AppRunError::IocBuildDependencyError(e))?;
-        log::info!("Services resolved [{count}]", count = services.len());
+        // failed start stopped like regular one: started services stopped, post stop hooks applied, pid file released
+        if let Err(err) = self.start().await {
+            if let Err(stop_err) = self.stop().await {
+                host_event!(error, "Application stop after start error failed", error = stop_err);
+            }
 
-        self.start(&mut services).await?;
+            return Err(err);
+        }
 
         let lifetime_time_manager = self.root_ioc_context.resolve::<Arc<dyn ILifeTimeManager>>().await
             .expect("LifeTimeManager not found");
+        let completion_tracker = self.root_ioc_context.resolve::<Arc<CompletionTracker>>().await
+            .map_err(AppRunError::IocBuildDependencyError)?;
 
-        lifetime_time_manager.wait_for_stop().await;
+        completion_tracker.set_all_started();
 
-        self.stop(&mut services).await?;
+        let metrics = self.root_ioc_context.resolve::<Arc<Metrics>>().await
+            .map_err(AppRunError::IocBuildDependencyError)?;
+        let wait_started_at = self.clock.now();
 
-        Ok(())
+        let stop_reason = tokio::select! {
+            _ = lifetime_time_manager.wait_for_stop() => self.service_manager.take_stop_reason().unwrap_or(StopReason::LifeTimeManager),
+            _ = completion_tracker.wait_completed() => {
+                host_event!(info, "All tracked background services completed");
+                lifetime_time_manager.stop().await;
+                StopReason::Completed
+            },
+        };
+
+        self.event_bus.publish(HostEvent::StopRequested { reason: stop_reason });
+
+        metrics.record_duration(WAIT_FOR_STOP_DURATION, &[], self.clock.now() - wait_started_at);
+
+        let stop_result = self.stop().await;
+
+        // failure escalated past top-level supervisor is the stop cause
+        if let Some(failure) = self.service_manager.take_escalated_failure() {
+            return Err(AppRunError::ServiceFailed(failure));
+        }
+
+        stop_result
     }
 
     async fn apply_life_time_manager(&mut self) -> AppRunResult {
         match self.root_ioc_context.resolve::<Arc<dyn ILifeTimeManager>>().await {
             Err(BuildDependencyError::NotFound { .. }) => {
-                log::info!("Life time manager not found, use default [CtrlCLifeTimeManager]");
+                host_event!(info, "Life time manager not found, use default", life_time_manager = "CtrlCLifeTimeManager");
 
                 self.register_life_time_manager::<CtrlCLifeTimeManager>().await.map_err(|e| {
-                    return match e {
+                    match e {
                         AddLifeTimeManagerError::IocAddDependencyError(err) => AppRunError::IocAddDependencyError(err),
                         AddLifeTimeManagerError::IocMapComponentError(err) => AppRunError::IocMapComponentError(err),
                     }
@@ -146,103 +407,359 @@ impl Application {
         }
     }
 
+    /// Fail fast if other instance holds pid file lock
+    async fn apply_pid_file(&mut self) -> AppRunResult {
+        if let Some(path) = self.core_config.read().await.value.pid_file.clone() {
+            let pid_file = PidFile::acquire(&path).map_err(|err| match err {
+                PidFileError::AlreadyLocked { pid_file, pid } => AppRunError::AlreadyRunning { pid_file, pid },
+                PidFileError::IoError { pid_file, message } => AppRunError::PidFileError { pid_file, message },
+            })?;
+
+            self.pid_file = Some(pid_file);
+        }
+
+        Ok(())
+    }
+
+    /// Notify systemd if running as ```Type=notify``` unit or notifier set
+    #[cfg(unix)]
+    fn apply_systemd_notify(&mut self) {
+        if self.systemd_notifier.is_none() {
+            self.systemd_notifier = SystemdNotifier::from_env().map(Arc::new);
+        }
+
+        if let Some(notifier) = self.systemd_notifier.clone() {
+            host_event!(info, "Systemd notify enabled", watchdog_interval = notifier.get_watchdog_interval());
+            self.systemd_notify_tasks = Some(SystemdNotifyTasks::spawn(notifier, &self.event_bus, self.clock.clone()));
+        }
+    }
+
     async fn apply_startups(&mut self) -> AppRunResult {
-        log::info!("Apply startups ...");
+        host_event!(info, "Apply startups ...");
 
         let startups = self.root_ioc_context.resolve_collection::<Weak<RwLock<dyn IStartup>>>().await;
 
-        let mut startups = if let Err(BuildDependencyError::NotFound { .. }) = startups {
+        let startups = if let Err(BuildDependencyError::NotFound { .. }) = startups {
             Vec::new()
         } else {
-            let startups = startups.map_err(|e| AppRunError::IocBuildDependencyError(e))?;
-            startups
+            startups.map_err(AppRunError::IocBuildDependencyError)?
         };
 
-        for startup in startups.iter_mut() {
-            let sturtup = startup.upgrade().expect("Startup not exist in scope");
-            let mut startup_write_guard = sturtup.write().await;
+        let startups = startups.into_iter()
+            .map(|startup| startup.upgrade().expect("Startup not exist in scope"))
+            .collect();
+
+        let startups = order_startups(startups).await?;
+
+        for (startup, startup_type_info) in startups.iter() {
+            startup.write().await.configure_dependency(&mut self.root_ioc_context).await
+                .map_err(|err| AppRunError::StartupFailed { startup_type_info: startup_type_info.clone(), err })?;
 
-            startup_write_guard.configure_dependency(&mut self.root_ioc_context).await;
-            startup_write_guard.configure_application(self.core_config.clone(), self).await;
+            host_event!(info, "Startup dependencies configured", startup = startup_type_info.type_name);
+        }
+
+        for (startup, startup_type_info) in startups.iter() {
+            startup.write().await.configure_application(self.core_config.clone(), self).await
+                .map_err(|err| AppRunError::StartupFailed { startup_type_info: startup_type_info.clone(), err })?;
 
-            log::info!("Startups applied [{service_info:?}]", service_info = startup_write_guard.get_type_info());
+            host_event!(info, "Startup applied", startup = startup_type_info.type_name);
+            self.event_bus.publish(HostEvent::StartupApplied { startup_type_info: startup_type_info.clone() });
         }
 
-        log::info!("Startups applied [{count}]", count = startups.len());
+        host_event!(info, "Startups applied", count = startups.len());
 
         Ok(())
     }
 
-    async fn start(&mut self, services: &mut Vec<Arc<RwLock<dyn IBaseService>>>) -> AppRunResult {
-        log::info!("Application starting ...");
+    async fn apply_pre_start_hooks(&mut self) -> AppRunResult {
+        let hooks = self.root_ioc_context.resolve_collection::<Arc<RwLock<dyn IPreStartHook>>>().await;
+
+        let hooks = if let Err(BuildDependencyError::NotFound { .. }) = hooks {
+            Vec::new()
+        } else {
+            hooks.map_err(AppRunError::IocBuildDependencyError)?
+        };
+
+        host_event!(info, "Apply pre start hooks ...", count = hooks.len());
+
+        let pre_start_hook_timeout = self.core_config.read().await.value.pre_start_hook_timeout;
+
+        for hook in hooks.into_iter() {
+            let hook_read_guard = hook.read().await;
+            let hook_type_info = hook_read_guard.get_type_info();
+            let timeout_duration = hook_read_guard.get_timeout().unwrap_or(pre_start_hook_timeout);
+            drop(hook_read_guard);
 
-        let on_start_timeout = self.core_config.read().await.value.on_start_timeout.clone();
+            host_event!(info, "Applying pre start hook ...", hook = hook_type_info.type_name);
 
-        let mut service_start_tasks = Vec::new();
-        for service in services.iter() {
-            let service = service.clone();
+            let hook_task = tokio::spawn(async move {
+                hook.write().await.on_pre_start().await
+            });
 
-            let service_type_info = service.read().await.get_type_info();
-            log::info!("Starting service ... [{service_type_info:?}]");
+            match self.clock.timeout(timeout_duration, hook_task).await {
+                Err(_) => {
+                    host_event!(error, "Pre start hook timeout expired", hook = hook_type_info.type_name);
+                    return Err(AppRunError::PreStartHookTimeoutExpired { timeout_duration, hook_type_info });
+                },
+                Ok(result) => {
+                    if let Err(err) = result.unwrap_or_else(|e| Err(HookError::Panic { message: e.to_string() })) {
+                        host_event!(error, "Pre start hook error", hook = hook_type_info.type_name, error = err);
+                        return Err(AppRunError::PreStartHookFailed { hook_type_info, err });
+                    }
+                },
+            }
 
-            let on_start_task = timeout(on_start_timeout.clone(), tokio::spawn(async move {
-                let mut service_write_guard = service.write().await;
-                service_write_guard.on_start().await;
-            }));
-            
-            service_start_tasks.push((on_start_task, service_type_info))
+            host_event!(info, "Pre start hook applied", hook = hook_type_info.type_name);
         }
 
-        for (task_handler, service_type_info) in service_start_tasks.into_iter() {
-            if task_handler.await.is_err() {
-                log::error!("Service start error [{service_type_info:?}]");
+        Ok(())
+    }
+
+    /// All hooks applied, first error returned
+    async fn apply_post_stop_hooks(&mut self) -> AppRunResult {
+        let hooks = self.root_ioc_context.resolve_collection::<Arc<RwLock<dyn IPostStopHook>>>().await;
+
+        let hooks = if let Err(BuildDependencyError::NotFound { .. }) = hooks {
+            Vec::new()
+        } else {
+            hooks.map_err(AppRunError::IocBuildDependencyError)?
+        };
 
-                return Err(AppRunError::ServiceStartTimeoutExpired { timeout_duration: on_start_timeout, service_type_info });
+        host_event!(info, "Apply post stop hooks ...", count = hooks.len());
+
+        let post_stop_hook_timeout = self.core_config.read().await.value.post_stop_hook_timeout;
+
+        let mut first_error = None;
+        for hook in hooks.into_iter() {
+            let hook_read_guard = hook.read().await;
+            let hook_type_info = hook_read_guard.get_type_info();
+            let timeout_duration = hook_read_guard.get_timeout().unwrap_or(post_stop_hook_timeout);
+            drop(hook_read_guard);
+
+            host_event!(info, "Applying post stop hook ...", hook = hook_type_info.type_name);
+
+            let hook_task = tokio::spawn(async move {
+                hook.write().await.on_post_stop().await
+            });
+
+            let error = match self.clock.timeout(timeout_duration, hook_task).await {
+                Err(_) => {
+                    host_event!(error, "Post stop hook timeout expired", hook = hook_type_info.type_name);
+                    Some(AppRunError::PostStopHookTimeoutExpired { timeout_duration, hook_type_info })
+                },
+                Ok(result) => match result.unwrap_or_else(|e| Err(HookError::Panic { message: e.to_string() })) {
+                    Err(err) => {
+                        host_event!(error, "Post stop hook error", hook = hook_type_info.type_name, error = err);
+                        Some(AppRunError::PostStopHookFailed { hook_type_info, err })
+                    },
+                    Ok(()) => {
+                        host_event!(info, "Post stop hook applied", hook = hook_type_info.type_name);
+                        None
+                    },
+                },
+            };
+
+            if first_error.is_none() {
+                first_error = error;
             }
+        }
+
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Add registered services, apply pre start hooks and start services. Services started before error are stopped by ```stop```
+    async fn start(&mut self) -> AppRunResult {
+        host_event!(info, "Resolving services ...");
+        let services = self.root_ioc_context.resolve_collection::<Arc<RwLock<dyn IBaseService>>>().await;
+
+        let services = if let Err(BuildDependencyError::NotFound { .. }) = services {
+            Vec::new()
+        } else {
+            services.map_err(AppRunError::IocBuildDependencyError)?
+        };
+        host_event!(info, "Services resolved", count = services.len());
+
+        for service in services.into_iter() {
+            self.service_manager.add_service_ref(service).await;
+        }
+
+        self.apply_pre_start_hooks().await?;
+
+        host_event!(info, "Application starting ...");
 
-            log::info!("Service started [{service_type_info:?}]");
+        if let Err(err) = self.service_manager.start_all().await {
+            host_event!(error, "Application start error", error = err);
+            return Err(map_service_manager_error(err));
         }
 
-        log::info!("Application started");
+        host_event!(info, "Application started");
+        self.event_bus.publish(HostEvent::ApplicationStarted);
 
         Ok(())
     }
 
-    async fn stop(&mut self, services: &mut Vec<Arc<RwLock<dyn IBaseService>>>) -> AppRunResult {
-        log::info!("Application stopping ...");
+    /// Shutdown phases: drain, stop, abort of remaining tasks. Abort request from life time manager skips to abort phase
+    async fn stop(&mut self) -> AppRunResult {
+        host_event!(info, "Application stopping ...");
+
+        let shutdown_signal = self.root_ioc_context.resolve::<Arc<ShutdownSignal>>().await
+            .map_err(AppRunError::IocBuildDependencyError)?;
+        let life_time_manager = self.root_ioc_context.resolve::<Arc<dyn ILifeTimeManager>>().await
+            .map_err(AppRunError::IocBuildDependencyError)?;
+
+        let drain_timeout = self.core_config.read().await.value.drain_timeout;
+        let service_manager = self.service_manager.clone();
+
+        let graceful_stop = async {
+            shutdown_signal.set_phase(ShutdownPhase::Draining);
+            service_manager.drain_all(drain_timeout).await;
+
+            shutdown_signal.set_phase(ShutdownPhase::Stopping);
+            service_manager.stop_all().await.map_err(map_service_manager_error)
+        };
+
+        let stop_result = tokio::select! {
+            stop_result = graceful_stop => stop_result,
+            _ = life_time_manager.wait_for_abort() => {
+                host_event!(warn, "Abort requested, skip graceful stop");
+                self.event_bus.publish(HostEvent::StopRequested { reason: StopReason::Abort });
+                Ok(())
+            },
+        };
 
-        let on_stop_timeout = self.core_config.read().await.value.on_stop_timeout.clone();
+        shutdown_signal.set_phase(ShutdownPhase::Aborting);
+        self.service_manager.abort_all().await;
 
-        let mut service_stop_tasks = Vec::new();
-        for service in services.iter() {
-            let service = service.clone();
-            
-            let service_type_info = service.write().await.get_type_info(); 
-            log::info!("Stopping service ... [{service_type_info:?}]");
+        // post stop hooks applied even if service stop failed
+        let post_stop_hooks_result = self.apply_post_stop_hooks().await;
 
-            let on_stop_task = timeout(on_stop_timeout.clone(), tokio::spawn(async move {
-                let mut service_write_guard = service.write().await;
-                service_write_guard.on_stop().await;
-            }));
-            
-            service_stop_tasks.push((on_stop_task, service_type_info))
+        if let Some(mut pid_file) = self.pid_file.take() {
+            pid_file.release();
         }
 
-        for (task_handler, service_type_info) in service_stop_tasks.into_iter() {
-            if task_handler.await.is_err() {
-                log::error!("Service stop error [{service_type_info:?}]");
+        shutdown_signal.set_phase(ShutdownPhase::Stopped);
 
-                return Err(AppRunError::ServiceStopTimeoutExpired { timeout_duration: on_stop_timeout, service_type_info });
-            }
+        // core config changes stored only after clean stop
+        let result = match stop_result.and(post_stop_hooks_result) {
+            Ok(()) => {
+                host_event!(info, "Store CoreConfig changes ...");
+                self.core_config.write().await.store().await.map_err(AppRunError::LoadConfigurationError)
+            },
+            Err(err) => Err(err),
+        };
+
+        // stopped event published and systemd notified even if stop failed
+        host_event!(info, "Application stopped");
+        self.event_bus.publish(HostEvent::ApplicationStopped);
 
-            log::info!("Service stopped [{service_type_info:?}]");
+        #[cfg(unix)]
+        if let Some(systemd_notify_tasks) = self.systemd_notify_tasks.take() {
+            systemd_notify_tasks.finish().await;
         }
 
-        log::info!("Store CoreConfig changes ...");
-        self.core_config.write().await.store().await.map_err(|e| AppRunError::LoadConfigurationError(e))?;
+        result
+    }
+}
 
-        log::info!("Application stopped");
+/// Host services shared by application, service manager and services
+async fn register_host_services(root_ioc_context: &DependencyContext) -> BuildDependencyResult<()> {
+    root_ioc_context.register_type::<RwLock<CoreConfigSnapshot>>(DependencyLifeCycle::Singleton).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
 
-        Ok(())
+    root_ioc_context.register_instance(ShutdownSignal::default()).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+
+    root_ioc_context.register_instance(CompletionTracker::default()).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+
+    root_ioc_context.register_instance(Clock::default()).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+
+    root_ioc_context.register_instance(MetricsRegistry::default()).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+
+    let metrics_registry: Arc<MetricsRegistry> = root_ioc_context.resolve().await?;
+    root_ioc_context.register_instance(Metrics::new(metrics_registry)).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+
+    root_ioc_context.register_instance(HostEventBus::default()).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+
+    #[cfg(unix)]
+    root_ioc_context.register_instance(ActivatedSockets::default()).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+
+    root_ioc_context.register_type::<ServiceScope>(DependencyLifeCycle::Transient).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+    root_ioc_context.register_type::<ServiceScopeFactory>(DependencyLifeCycle::Singleton).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+    root_ioc_context.register_type::<HeartbeatRegistry>(DependencyLifeCycle::Singleton).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+    root_ioc_context.register_instance(TaskTracker::default()).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+    root_ioc_context.register_type::<TaskSpawner>(DependencyLifeCycle::Transient).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+
+    root_ioc_context.register_type::<ServiceManager>(DependencyLifeCycle::Singleton).await
+        .map_err(|e| BuildDependencyError::AddDependencyError {err: e})?;
+
+    Ok(())
+}
+
+/// Runtime from ```CoreConfig::runtime```, configuration loaded on temporary current thread runtime
+fn build_runtime(configuration_source: &ConfigurationSource<CoreConfig>) -> Result<Runtime, RunBlockingError> {
+    let mut bootstrap_source = configuration_source.clone();
+    let runtime_config = Builder::new_current_thread().build().map_err(RunBlockingError::RuntimeBuildError)?
+        .block_on(async move { bootstrap_source.get().await })
+        .map_err(RunBlockingError::LoadConfigurationError)?
+        .runtime;
+
+    runtime_config.build().map_err(RunBlockingError::RuntimeBuildError)
+}
+
+/// Order by dependencies, then by priority, then by registration order
+async fn order_startups(startups: Vec<Arc<RwLock<dyn IStartup>>>) -> Result<Vec<(Arc<RwLock<dyn IStartup>>, TypeInfo)>, AppRunError> {
+    let mut pending = Vec::new();
+    for startup in startups.into_iter() {
+        let startup_read_guard = startup.read().await;
+        let type_info = startup_read_guard.get_type_info();
+        let priority = startup_read_guard.get_priority();
+        let dependencies = startup_read_guard.get_dependencies();
+        drop(startup_read_guard);
+
+        pending.push((startup, type_info, priority, dependencies));
+    }
+
+    for (_, type_info, _, dependencies) in pending.iter() {
+        if let Some(dependency) = dependencies.iter().find(|dependency| !pending.iter().any(|(_, type_info, _, _)| type_info.type_id == dependency.type_id)) {
+            return Err(AppRunError::StartupDependencyNotFound { startup_type_info: type_info.clone(), dependency_type_info: dependency.clone() });
+        }
+    }
+
+    let mut ordered: Vec<(Arc<RwLock<dyn IStartup>>, TypeInfo)> = Vec::new();
+    while !pending.is_empty() {
+        let next = pending.iter().enumerate()
+            .filter(|(_, (_, _, _, dependencies))| dependencies.iter().all(|dependency| ordered.iter().any(|(_, type_info)| type_info.type_id == dependency.type_id)))
+            .min_by_key(|(_, (_, _, priority, _))| *priority)
+            .map(|(index, _)| index);
+
+        let Some(next) = next else {
+            return Err(AppRunError::StartupCyclicDependency { startup_type_info: pending[0].1.clone() });
+        };
+
+        let (startup, type_info, _, _) = pending.remove(next);
+        ordered.push((startup, type_info));
+    }
+
+    Ok(ordered)
+}
+
+fn map_service_manager_error(err: ServiceManagerError) -> AppRunError {
+    match err {
+        ServiceManagerError::ServiceStartTimeoutExpired { timeout_duration, service_type_info } => AppRunError::ServiceStartTimeoutExpired { timeout_duration, service_type_info },
+        ServiceManagerError::ServiceStopTimeoutExpired { timeout_duration, service_type_info } => AppRunError::ServiceStopTimeoutExpired { timeout_duration, service_type_info },
+        ServiceManagerError::ServiceStartFailed { service_type_info, message } => AppRunError::ServiceStartFailed { service_type_info, message },
+        ServiceManagerError::ServiceStopFailed { service_type_info, message } => AppRunError::ServiceStopFailed { service_type_info, message },
+        err => AppRunError::ServiceManagerError(err),
     }
-}
\ No newline at end of file
+}
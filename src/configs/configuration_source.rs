@@ -0,0 +1,46 @@
+use anthill_di_configuration_extension::{
+    ConfigurationSnapshot,
+    source::{ISource, JsonFileConfiguration},
+    types::{LoadConfigurationResult, SyncConfigurationResult},
+};
+use serde::{Deserialize, Serialize};
+
+use super::InMemoryConfiguration;
+
+/// Configuration snapshot registered by ```Application::register_configuration```. Resolve as ```Arc<RwLock<ConfigSnapshot<TConfiguration>>>```
+pub type ConfigSnapshot<TConfiguration> = ConfigurationSnapshot<TConfiguration, ConfigurationSource<TConfiguration>>;
+
+/// Configuration source selected at application creation or configuration registration
+#[derive(Clone)]
+pub enum ConfigurationSource<TConfiguration> where for<'de> TConfiguration: Deserialize<'de> + Serialize + Default + Sync + Send + 'static {
+    JsonFile(JsonFileConfiguration<TConfiguration>),
+    InMemory(InMemoryConfiguration<TConfiguration>),
+}
+
+impl<TConfiguration> ConfigurationSource<TConfiguration> where for<'de> TConfiguration: Deserialize<'de> + Serialize + Default + Sync + Send + 'static {
+    /// Json file created with default configuration if not exist
+    pub fn json_file(path: &str) -> Self {
+        Self::JsonFile(JsonFileConfiguration::new(path.to_string(), true))
+    }
+
+    pub fn in_memory(configuration: &TConfiguration) -> Self {
+        Self::InMemory(InMemoryConfiguration::new(configuration))
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TConfiguration> ISource<TConfiguration> for ConfigurationSource<TConfiguration> where for<'de> TConfiguration: Deserialize<'de> + Serialize + Default + Sync + Send + 'static {
+    async fn get(&mut self) -> LoadConfigurationResult<TConfiguration> {
+        match self {
+            Self::JsonFile(source) => source.get().await,
+            Self::InMemory(source) => source.get().await,
+        }
+    }
+
+    async fn set(&self, configuration: &TConfiguration) -> SyncConfigurationResult {
+        match self {
+            Self::JsonFile(source) => source.set(configuration).await,
+            Self::InMemory(source) => source.set(configuration).await,
+        }
+    }
+}
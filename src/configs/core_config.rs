@@ -1,21 +1,62 @@
 use std::time::Duration;
 use serde::{Deserialize, Serialize};
 
-#[derive(Deserialize, Serialize)]
+use super::{ConfigSnapshot, RuntimeConfig};
+
+/// Application core configuration snapshot. Resolve as ```Arc<RwLock<CoreConfigSnapshot>>```
+pub type CoreConfigSnapshot = ConfigSnapshot<CoreConfig>;
+
+#[derive(Deserialize, Serialize, Clone)]
 pub struct CoreConfig {
     #[serde(default = "default_timeout")]
     pub on_start_timeout: Duration,
 
     #[serde(default = "default_timeout")]
     pub on_stop_timeout: Duration,
+
+    /// Shutdown drain phase deadline, see ```ShutdownPhase::Draining```
+    #[serde(default = "default_timeout")]
+    pub drain_timeout: Duration,
+
+    #[serde(default = "default_timeout")]
+    pub pre_start_hook_timeout: Duration,
+
+    #[serde(default = "default_timeout")]
+    pub post_stop_hook_timeout: Duration,
+
+    /// Pid file with single instance lock, created on run and removed on stop. Disabled if not set
+    /// Lock is unix only, on other platforms pid written but second instance not detected
+    #[serde(default)]
+    pub pid_file: Option<String>,
+
+    /// Runtime created by ```Application::run_blocking```
+    #[serde(default)]
+    pub runtime: RuntimeConfig,
+
+    /// Runtime created for every ```IsolatedService```, thread name defaults to service type name
+    #[serde(default = "default_isolated_runtime")]
+    pub isolated_runtime: RuntimeConfig,
 }
 
 fn default_timeout() -> Duration {
     Duration::from_millis(5000)
 }
 
+fn default_isolated_runtime() -> RuntimeConfig {
+    RuntimeConfig { worker_threads: Some(1), ..Default::default() }
+}
+
 impl Default for CoreConfig {
     fn default() -> Self {
-        Self { on_start_timeout: Duration::from_millis(5000), on_stop_timeout: Duration::from_millis(5000) }
+        Self {
+            on_start_timeout: Duration::from_millis(5000),
+            on_stop_timeout: Duration::from_millis(5000),
+            drain_timeout: Duration::from_millis(5000),
+            pre_start_hook_timeout: Duration::from_millis(5000),
+            post_stop_hook_timeout: Duration::from_millis(5000),
+            pid_file: None,
+            runtime: RuntimeConfig::default(),
+            isolated_runtime: default_isolated_runtime(),
+        }
     }
 } 
\ No newline at end of file
@@ -0,0 +1,51 @@
+use anthill_di_configuration_extension::{
+    source::ISource,
+    types::{LoadConfigurationResult, LoadConfigurationError, SyncConfigurationResult},
+};
+use serde::{Deserialize, Serialize};
+use std::{marker::PhantomData, sync::{Arc, Mutex}};
+
+/// Configuration source without disk I/O. Stored as json, so load and store behave like ```JsonFileConfiguration```.
+/// Clones share stored value
+pub struct InMemoryConfiguration<TConfiguration> where for<'de> TConfiguration: Deserialize<'de> + Serialize + Default + Sync + Send + 'static {
+    data: Arc<Mutex<String>>,
+    pd: PhantomData<fn() -> TConfiguration>,
+}
+
+impl<TConfiguration> Clone for InMemoryConfiguration<TConfiguration> where for<'de> TConfiguration: Deserialize<'de> + Serialize + Default + Sync + Send + 'static {
+    fn clone(&self) -> Self {
+        Self { data: self.data.clone(), pd: PhantomData }
+    }
+}
+
+impl<TConfiguration> Default for InMemoryConfiguration<TConfiguration> where for<'de> TConfiguration: Deserialize<'de> + Serialize + Default + Sync + Send + 'static {
+    fn default() -> Self {
+        Self::new(&TConfiguration::default())
+    }
+}
+
+impl<TConfiguration> InMemoryConfiguration<TConfiguration> where for<'de> TConfiguration: Deserialize<'de> + Serialize + Default + Sync + Send + 'static {
+    pub fn new(configuration: &TConfiguration) -> Self {
+        Self {
+            data: Arc::new(Mutex::new(serde_json::to_string(configuration).unwrap())),
+            pd: PhantomData,
+        }
+    }
+
+    /// Last stored configuration
+    pub fn get_value(&self) -> LoadConfigurationResult<TConfiguration> {
+        serde_json::from_str(&self.data.lock().unwrap()).map_err(LoadConfigurationError::TokioError)
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TConfiguration> ISource<TConfiguration> for InMemoryConfiguration<TConfiguration> where for<'de> TConfiguration: Deserialize<'de> + Serialize + Default + Sync + Send + 'static {
+    async fn get(&mut self) -> LoadConfigurationResult<TConfiguration> {
+        self.get_value()
+    }
+
+    async fn set(&self, configuration: &TConfiguration) -> SyncConfigurationResult {
+        *self.data.lock().unwrap() = serde_json::to_string(configuration).map_err(LoadConfigurationError::TokioError)?;
+        Ok(())
+    }
+}
@@ -1,2 +1,14 @@
 mod core_config;
-pub use core_config::*;
\ No newline at end of file
+pub use core_config::*;
+
+mod runtime_config;
+pub use runtime_config::*;
+
+mod process_config;
+pub use process_config::*;
+
+mod in_memory_configuration;
+pub use in_memory_configuration::*;
+
+mod configuration_source;
+pub use configuration_source::*;
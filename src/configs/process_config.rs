@@ -0,0 +1,75 @@
+use std::{collections::BTreeMap, time::Duration};
+use serde::{Deserialize, Serialize};
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ProcessRestartPolicy {
+    Never,
+    /// Restart if process exit status is not success
+    OnFailure,
+    Always,
+}
+
+/// External command run by ```ProcessService```
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct ProcessConfig {
+    /// Used in logs and events, program if not set
+    #[serde(default)]
+    pub name: Option<String>,
+
+    pub program: String,
+
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+
+    #[serde(default)]
+    pub working_dir: Option<String>,
+
+    #[serde(default = "default_restart_policy")]
+    pub restart_policy: ProcessRestartPolicy,
+
+    /// Unlimited if not set
+    #[serde(default)]
+    pub max_restarts: Option<usize>,
+
+    #[serde(default = "default_restart_delay")]
+    pub restart_delay: Duration,
+
+    /// SIGTERM to SIGKILL delay on stop, should be less than ```CoreConfig::on_stop_timeout```
+    #[serde(default = "default_stop_timeout")]
+    pub stop_timeout: Duration,
+}
+
+fn default_restart_policy() -> ProcessRestartPolicy {
+    ProcessRestartPolicy::OnFailure
+}
+
+fn default_restart_delay() -> Duration {
+    Duration::from_millis(1000)
+}
+
+fn default_stop_timeout() -> Duration {
+    Duration::from_millis(3000)
+}
+
+impl ProcessConfig {
+    pub fn new(program: &str) -> Self {
+        Self {
+            name: None,
+            program: program.to_string(),
+            args: Vec::new(),
+            env: BTreeMap::new(),
+            working_dir: None,
+            restart_policy: default_restart_policy(),
+            max_restarts: None,
+            restart_delay: default_restart_delay(),
+            stop_timeout: default_stop_timeout(),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        self.name.as_deref().unwrap_or(&self.program)
+    }
+}
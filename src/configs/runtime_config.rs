@@ -0,0 +1,66 @@
+use serde::{Deserialize, Serialize};
+use tokio::runtime::{Builder, Runtime};
+
+#[derive(Deserialize, Serialize, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuntimeFlavor {
+    CurrentThread,
+    MultiThread,
+}
+
+/// Tokio runtime settings, used by ```Application::run_blocking``` and ```IsolatedService```
+#[derive(Deserialize, Serialize, Clone, Debug, PartialEq, Eq)]
+pub struct RuntimeConfig {
+    #[serde(default = "default_flavor")]
+    pub flavor: RuntimeFlavor,
+
+    /// Multi thread runtime worker threads, number of cores if not set
+    #[serde(default)]
+    pub worker_threads: Option<usize>,
+
+    #[serde(default)]
+    pub thread_name: Option<String>,
+
+    #[serde(default)]
+    pub thread_stack_size: Option<usize>,
+}
+
+fn default_flavor() -> RuntimeFlavor {
+    RuntimeFlavor::MultiThread
+}
+
+impl Default for RuntimeConfig {
+    fn default() -> Self {
+        Self {
+            flavor: RuntimeFlavor::MultiThread,
+            worker_threads: None,
+            thread_name: None,
+            thread_stack_size: None,
+        }
+    }
+}
+
+impl RuntimeConfig {
+    /// Build runtime with all drivers (io, time) enabled
+    pub fn build(&self) -> std::io::Result<Runtime> {
+        let mut builder = match self.flavor {
+            RuntimeFlavor::CurrentThread => Builder::new_current_thread(),
+            RuntimeFlavor::MultiThread => Builder::new_multi_thread(),
+        };
+
+        builder.enable_all();
+
+        if let (RuntimeFlavor::MultiThread, Some(worker_threads)) = (self.flavor, self.worker_threads) {
+            builder.worker_threads(worker_threads);
+        }
+
+        if let Some(thread_name) = &self.thread_name {
+            builder.thread_name(thread_name);
+        }
+
+        if let Some(thread_stack_size) = self.thread_stack_size {
+            builder.thread_stack_size(thread_stack_size);
+        }
+
+        builder.build()
+    }
+}
@@ -0,0 +1,45 @@
+use std::time::Duration;
+
+use anthill_di::types::TypeInfo;
+
+use crate::services::ServiceId;
+
+/// Why application left ```wait_for_stop``` or skipped graceful stop
+#[derive(Debug, Clone, PartialEq)]
+pub enum StopReason {
+    /// Life time manager requested stop (Ctrl-C, deadline, manual stop, ...)
+    LifeTimeManager,
+    /// All tracked background services completed in run to completion mode
+    Completed,
+    /// Top-level supervisor escalated child failure
+    SupervisorEscalated { supervisor_type_info: TypeInfo },
+    /// Stall watchdog applied ```StallAction::StopApplication```
+    ServiceStalled { service_type_info: TypeInfo },
+    /// Life time manager requested abort during graceful stop (second Ctrl-C), published after first stop request
+    Abort,
+}
+
+/// Typed host event published by ```HostEventBus```
+#[derive(Debug, Clone, PartialEq)]
+pub enum HostEvent {
+    ServiceRegistered { service_id: ServiceId, service_type_info: TypeInfo },
+    ServiceStarting { service_id: ServiceId, service_type_info: TypeInfo },
+    ServiceStarted { service_id: ServiceId, service_type_info: TypeInfo },
+    ServiceFailed { service_id: ServiceId, service_type_info: TypeInfo },
+    ServiceStopping { service_id: ServiceId, service_type_info: TypeInfo },
+    ServiceStopped { service_id: ServiceId, service_type_info: TypeInfo },
+    ServiceAborted { service_id: ServiceId, service_type_info: TypeInfo },
+    StartupApplied { startup_type_info: TypeInfo },
+    ConfigurationReloaded { configuration_type_info: TypeInfo },
+    /// ```ProcessService``` child process spawned
+    ProcessStarted { name: String, pid: Option<u32> },
+    /// ```ProcessService``` child process exited, ```exit_code``` is not set if killed by signal
+    ProcessExited { name: String, exit_code: Option<i32>, restart: bool },
+    /// Heartbeat older than deadline, see ```StallWatchdog```
+    ServiceUnhealthy { service_id: ServiceId, service_type_info: TypeInfo, stalled_for: Duration },
+    /// Heartbeat received after ```ServiceUnhealthy``` or stalled service restarted
+    ServiceHealthy { service_id: ServiceId, service_type_info: TypeInfo },
+    ApplicationStarted,
+    StopRequested { reason: StopReason },
+    ApplicationStopped,
+}
@@ -0,0 +1,26 @@
+use tokio::sync::broadcast;
+
+use super::HostEvent;
+
+/// Broadcast bus of typed host events, resolve as ```Arc<HostEventBus>```.
+/// Events published before subscription are not received, slow subscriber receives ```RecvError::Lagged```
+pub struct HostEventBus {
+    sender: broadcast::Sender<HostEvent>,
+}
+
+impl Default for HostEventBus {
+    fn default() -> Self {
+        Self { sender: broadcast::channel(1024).0 }
+    }
+}
+
+impl HostEventBus {
+    /// Publish event to all current subscribers, no subscribers is not error
+    pub fn publish(&self, event: HostEvent) {
+        _ = self.sender.send(event);
+    }
+
+    pub fn subscribe(&self) -> broadcast::Receiver<HostEvent> {
+        self.sender.subscribe()
+    }
+}
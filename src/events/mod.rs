@@ -0,0 +1,5 @@
+mod host_event;
+pub use host_event::*;
+
+mod host_event_bus;
+pub use host_event_bus::*;
@@ -0,0 +1,5 @@
+mod pre_start_hook;
+pub use pre_start_hook::*;
+
+mod post_stop_hook;
+pub use post_stop_hook::*;
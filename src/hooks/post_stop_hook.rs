@@ -0,0 +1,20 @@
+use std::time::Duration;
+
+use anthill_di::types::TypeInfo;
+
+use crate::types::HookResult;
+
+/// Called after every service ```on_stop```, all hooks called even if one fails
+#[async_trait_with_sync::async_trait(Sync)]
+pub trait IPostStopHook where Self: Sync + Send + 'static {
+    async fn on_post_stop(&mut self) -> HookResult;
+
+    fn get_type_info(&self) -> TypeInfo {
+        TypeInfo::from_type::<Self>()
+    }
+
+    /// Override ```CoreConfig::post_stop_hook_timeout``` for this hook
+    fn get_timeout(&self) -> Option<Duration> {
+        None
+    }
+}
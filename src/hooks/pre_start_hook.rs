@@ -0,0 +1,20 @@
+use std::time::Duration;
+
+use anthill_di::types::TypeInfo;
+
+use crate::types::HookResult;
+
+/// Called before any service ```on_start```, error aborts application run
+#[async_trait_with_sync::async_trait(Sync)]
+pub trait IPreStartHook where Self: Sync + Send + 'static {
+    async fn on_pre_start(&mut self) -> HookResult;
+
+    fn get_type_info(&self) -> TypeInfo {
+        TypeInfo::from_type::<Self>()
+    }
+
+    /// Override ```CoreConfig::pre_start_hook_timeout``` for this hook
+    fn get_timeout(&self) -> Option<Duration> {
+        None
+    }
+}
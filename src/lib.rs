@@ -1,7 +1,14 @@
+#[macro_use]
+mod trace;
+
 pub mod configs;
 pub mod types;
 pub mod life_time;
 pub mod services;
+pub mod hooks;
+pub mod time;
+pub mod metrics;
+pub mod events;
 
 mod application;
 pub use application::*;
@@ -10,4 +17,37 @@ pub use application::*;
 mod startup;
 pub use startup::*;
 
+mod pid_file;
+pub use pid_file::*;
+
+#[cfg(unix)]
+mod systemd_notify;
+#[cfg(unix)]
+pub use systemd_notify::*;
+
+#[cfg(unix)]
+mod socket_activation;
+#[cfg(unix)]
+pub use socket_activation::*;
+
+/// Link time collected registrations, enabled with ```macros``` feature
+#[cfg(feature = "macros")]
+pub mod registration;
+
+#[cfg(feature = "macros")]
+pub use anthill_service_system_derive::{main, service, background_service, startup};
+
+#[cfg(feature = "macros")]
+#[doc(hidden)]
+pub use inventory;
+
+// generated code refers to ::anthill_service_system, also inside this crate
+#[cfg(feature = "macros")]
+extern crate self as anthill_service_system;
+
+/// Test host harness, enabled with ```testing``` feature
+#[cfg(any(test, feature = "testing"))]
+pub mod testing;
+
+#[cfg(test)]
 pub (crate) mod tests;
\ No newline at end of file
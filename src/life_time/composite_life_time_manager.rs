@@ -0,0 +1,196 @@
+use anthill_di::{Constructor, DependencyContext, DependencyLifeCycle, types::{BuildDependencyResult, TypeInfo}};
+use tokio::{sync::watch, task::JoinSet};
+use std::{
+    collections::HashMap,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+};
+
+use super::ILifeTimeManager;
+
+type LifeTimeManagerFactory = Arc<dyn for<'a> Fn(&'a DependencyContext) -> Pin<Box<dyn Future<Output = BuildDependencyResult<Arc<dyn ILifeTimeManager>>> + Send + Sync + 'a>> + Send + Sync>;
+
+/// Wrapper used to build composite children through ioc without mapping them as application life time manager
+struct CompositeChild<TLifeTimeManager>(TLifeTimeManager);
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TLifeTimeManager> Constructor for CompositeChild<TLifeTimeManager> where TLifeTimeManager: ILifeTimeManager + Constructor {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self(TLifeTimeManager::ctor(ctx).await?))
+    }
+}
+
+/// Composite life time manager description, register with ```Application::register_composite_life_time_manager```
+#[derive(Clone, Default)]
+pub struct CompositeLifeTimeManagerBuilder {
+    children: Vec<(TypeInfo, LifeTimeManagerFactory)>,
+}
+
+impl CompositeLifeTimeManagerBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Child created from ioc context, but not registered in it
+    pub fn add_life_time_manager<TLifeTimeManager: ILifeTimeManager + Constructor + 'static>(mut self) -> Self {
+        self.children.push((TypeInfo::from_type::<TLifeTimeManager>(), Arc::new(|ctx: &DependencyContext| Box::pin(async move {
+            // Error only if child type already built before
+            _ = ctx.register_type::<CompositeChild<TLifeTimeManager>>(DependencyLifeCycle::Transient).await;
+
+            let child = ctx.resolve::<CompositeChild<TLifeTimeManager>>().await?;
+            Ok(Arc::new(child.0) as Arc<dyn ILifeTimeManager>)
+        }))));
+        self
+    }
+
+    /// Child resolved from ioc as ```Arc<TLifeTimeManager>```, register it with ```register_instance``` to share it with services (like ```IdleLifeTimeManager```)
+    pub fn add_shared_life_time_manager<TLifeTimeManager: ILifeTimeManager + 'static>(mut self) -> Self {
+        self.children.push((TypeInfo::from_type::<TLifeTimeManager>(), Arc::new(|ctx: &DependencyContext| Box::pin(async move {
+            Ok(ctx.resolve::<Arc<TLifeTimeManager>>().await? as Arc<dyn ILifeTimeManager>)
+        }))));
+        self
+    }
+
+    pub fn add_life_time_manager_instance<TLifeTimeManager: ILifeTimeManager + 'static>(mut self, life_time_manager: TLifeTimeManager) -> Self {
+        let life_time_manager: Arc<dyn ILifeTimeManager> = Arc::new(life_time_manager);
+        self.children.push((TypeInfo::from_type::<TLifeTimeManager>(), Arc::new(move |_: &DependencyContext| {
+            let life_time_manager = life_time_manager.clone();
+            Box::pin(async move { Ok(life_time_manager) })
+        })));
+        self
+    }
+}
+
+/// Stop when first child stops, propagate stop to all children. Resolve as ```Arc<CompositeLifeTimeManager>``` to get stop trigger
+pub struct CompositeLifeTimeManager {
+    children: Vec<(TypeInfo, Arc<dyn ILifeTimeManager>)>,
+    stop_trigger: Mutex<Option<TypeInfo>>,
+    is_running_sender: watch::Sender<bool>,
+    is_running_receiver: watch::Receiver<bool>,
+}
+
+impl CompositeLifeTimeManager {
+    pub (crate) async fn build(builder: CompositeLifeTimeManagerBuilder, ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        let mut children = Vec::new();
+
+        for (type_info, factory) in builder.children.into_iter() {
+            children.push((type_info, factory(&ctx).await?));
+        }
+
+        let (is_running_sender, is_running_receiver) = watch::channel(true);
+
+        Ok(Self {
+            children,
+            stop_trigger: Mutex::new(None),
+            is_running_sender,
+            is_running_receiver,
+        })
+    }
+
+    /// Child which caused stop, or ```CompositeLifeTimeManager``` if stopped directly. ```None``` while running
+    pub fn get_stop_trigger(&self) -> Option<TypeInfo> {
+        self.stop_trigger.lock().unwrap().clone()
+    }
+
+    fn set_stop_trigger(&self, type_info: TypeInfo) {
+        let mut stop_trigger = self.stop_trigger.lock().unwrap();
+
+        // first trigger wins
+        if stop_trigger.is_none() {
+            host_event!(info, "Life time manager stop triggered", life_time_manager = type_info.type_name);
+            *stop_trigger = Some(type_info);
+        }
+    }
+
+    async fn stop_children(&self) {
+        _ = self.is_running_sender.send(false);
+
+        for (_, child) in self.children.iter() {
+            child.stop().await;
+        }
+    }
+
+    /// Wait until first child future completes, pending forever if no children. Panicked child wait treated as completed.
+    /// Child waits run in join set, so they are aborted when this future completes or dropped
+    async fn wait_any<TFuture, TWait>(&self, wait: TWait) -> TypeInfo
+    where
+        TFuture: Future<Output = ()> + Send + 'static,
+        TWait: Fn(Arc<dyn ILifeTimeManager>) -> TFuture,
+    {
+        let mut wait_tasks = JoinSet::new();
+        let mut task_indexes = HashMap::new();
+
+        for (index, (_, child)) in self.children.iter().enumerate() {
+            let wait_future = wait(child.clone());
+            let abort_handle = wait_tasks.spawn(wait_future);
+            task_indexes.insert(abort_handle.id(), index);
+        }
+
+        let Some(result) = wait_tasks.join_next_with_id().await else {
+            return std::future::pending().await;
+        };
+
+        let task_id = match result {
+            Ok((task_id, _)) => task_id,
+            Err(err) => {
+                let task_id = err.id();
+                host_event!(error, "Life time manager wait error", life_time_manager = self.children[task_indexes[&task_id]].0.type_name, error = err);
+                task_id
+            },
+        };
+
+        self.children[task_indexes[&task_id]].0.clone()
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl ILifeTimeManager for CompositeLifeTimeManager {
+    async fn stop(&self) {
+        self.set_stop_trigger(TypeInfo::from_type::<Self>());
+        self.stop_children().await;
+    }
+
+    async fn is_running(&self) -> bool {
+        if !*self.is_running_receiver.borrow() {
+            return false;
+        }
+
+        for (_, child) in self.children.iter() {
+            if !child.is_running().await {
+                return false;
+            }
+        }
+
+        true
+    }
+
+    async fn wait_for_stop(&self) {
+        let mut is_running_receiver = self.is_running_receiver.clone();
+
+        let stopped_directly = async move {
+            while *is_running_receiver.borrow() {
+                if is_running_receiver.changed().await.is_err() {
+                    return;
+                }
+            }
+        };
+
+        let stopped_by_child = self.wait_any(|child| async move { child.wait_for_stop().await });
+
+        let stop_trigger = tokio::select! {
+            type_info = stopped_by_child => Some(type_info),
+            _ = stopped_directly => None,
+        };
+
+        if let Some(type_info) = stop_trigger {
+            self.set_stop_trigger(type_info);
+            self.stop_children().await;
+        }
+    }
+
+    async fn wait_for_abort(&self) {
+        let type_info = self.wait_any(|child| async move { child.wait_for_abort().await }).await;
+        host_event!(warn, "Life time manager abort triggered", life_time_manager = type_info.type_name);
+    }
+}
@@ -3,21 +3,27 @@ use tokio::task::yield_now;
 use super::ILifeTimeManager;
 use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
 
+/// First ```ctrl+c``` request graceful stop, second ```ctrl+c``` request abort
 pub struct CtrlCLifeTimeManager {
     is_running: Arc<AtomicBool>,
+    is_aborted: Arc<AtomicBool>,
 }
 
 #[async_trait_with_sync::async_trait(Sync)]
 impl Constructor for CtrlCLifeTimeManager {
     async fn ctor(_: DependencyContext) -> BuildDependencyResult<Self> {
         let is_running = Arc::new(AtomicBool::new(true));
+        let is_aborted = Arc::new(AtomicBool::new(false));
 
         let is_running_clone = is_running.clone();
+        let is_aborted_clone = is_aborted.clone();
         ctrlc::set_handler(move || {
-            is_running_clone.store(false, Ordering::SeqCst);
+            if !is_running_clone.swap(false, Ordering::SeqCst) {
+                is_aborted_clone.store(true, Ordering::SeqCst);
+            }
         }).expect("Error setting Ctrl-C handler");
         
-        Ok(Self { is_running })
+        Ok(Self { is_running, is_aborted })
     }
 }
 
@@ -36,4 +42,10 @@ impl ILifeTimeManager for CtrlCLifeTimeManager {
             yield_now().await;
         }
     }
-}
\ No newline at end of file
+
+    async fn wait_for_abort(&self) {
+        while !self.is_aborted.load(Ordering::SeqCst) {
+            yield_now().await;
+        }
+    }
+}
@@ -0,0 +1,61 @@
+use tokio::{sync::watch, time::Instant};
+use super::ILifeTimeManager;
+use std::{sync::Arc, time::{Duration, SystemTime}};
+
+use crate::time::Clock;
+
+/// Stop application when deadline reached or on service request.
+/// Deadline measured by host ```Clock``` (```Application::clock```), so ```ManualClock``` moves it
+pub struct DeadlineLifeTimeManager {
+    deadline: Instant,
+    clock: Arc<Clock>,
+    is_running_sender: watch::Sender<bool>,
+    is_running_receiver: watch::Receiver<bool>,
+}
+
+impl DeadlineLifeTimeManager {
+    /// Deadline counted from manager creation
+    pub fn after(run_duration: Duration, clock: Arc<Clock>) -> Self {
+        let (is_running_sender, is_running_receiver) = watch::channel(true);
+        Self { deadline: clock.now() + run_duration, clock, is_running_sender, is_running_receiver }
+    }
+
+    /// Wall-clock deadline converted to run duration on creation, already passed deadline stops application immediately
+    pub fn at(deadline: SystemTime, clock: Arc<Clock>) -> Self {
+        let run_duration = deadline.duration_since(SystemTime::now()).unwrap_or(Duration::ZERO);
+        Self::after(run_duration, clock)
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl ILifeTimeManager for DeadlineLifeTimeManager {
+    async fn stop(&self) {
+        _ = self.is_running_sender.send(false);
+    }
+
+    async fn is_running(&self) -> bool {
+        *self.is_running_receiver.borrow() && self.clock.now() < self.deadline
+    }
+
+    async fn wait_for_stop(&self) {
+        let mut is_running_receiver = self.is_running_receiver.clone();
+
+        let stopped = async move {
+            while *is_running_receiver.borrow() {
+                if is_running_receiver.changed().await.is_err() {
+                    return;
+                }
+            }
+        };
+
+        let deadline_reached = tokio::select! {
+            _ = self.clock.sleep_until(self.deadline) => true,
+            _ = stopped => false,
+        };
+
+        if deadline_reached {
+            host_event!(info, "Deadline reached, stop application");
+            _ = self.is_running_sender.send(false);
+        }
+    }
+}
@@ -0,0 +1,86 @@
+use tokio::{sync::watch, time::Instant};
+use super::ILifeTimeManager;
+use std::{sync::{Arc, Mutex}, time::Duration};
+
+use crate::time::Clock;
+
+/// Stop application when no activity reported during idle timeout or on service request.
+/// Register as instance and resolve ```Arc<IdleLifeTimeManager>``` in services to report activity,
+/// inside composite add with ```CompositeLifeTimeManagerBuilder::add_shared_life_time_manager```.
+/// Idle period measured by host ```Clock``` (```Application::clock```)
+pub struct IdleLifeTimeManager {
+    idle_timeout: Duration,
+    clock: Arc<Clock>,
+    last_activity: Mutex<Instant>,
+    is_running_sender: watch::Sender<bool>,
+    is_running_receiver: watch::Receiver<bool>,
+}
+
+impl IdleLifeTimeManager {
+    /// Idle period counted from manager creation until first activity
+    pub fn new(idle_timeout: Duration, clock: Arc<Clock>) -> Self {
+        let (is_running_sender, is_running_receiver) = watch::channel(true);
+
+        Self {
+            idle_timeout,
+            last_activity: Mutex::new(clock.now()),
+            clock,
+            is_running_sender,
+            is_running_receiver,
+        }
+    }
+
+    /// Reset idle period
+    pub fn report_activity(&self) {
+        *self.last_activity.lock().unwrap() = self.clock.now();
+    }
+
+    fn get_idle_deadline(&self) -> Instant {
+        *self.last_activity.lock().unwrap() + self.idle_timeout
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl ILifeTimeManager for IdleLifeTimeManager {
+    async fn stop(&self) {
+        _ = self.is_running_sender.send(false);
+    }
+
+    async fn is_running(&self) -> bool {
+        *self.is_running_receiver.borrow() && self.clock.now() < self.get_idle_deadline()
+    }
+
+    async fn wait_for_stop(&self) {
+        let mut is_running_receiver = self.is_running_receiver.clone();
+
+        let stopped = async move {
+            while *is_running_receiver.borrow() {
+                if is_running_receiver.changed().await.is_err() {
+                    return;
+                }
+            }
+        };
+
+        let wait_idle = async {
+            // activity may move deadline while sleeping
+            loop {
+                let idle_deadline = self.get_idle_deadline();
+                self.clock.sleep_until(idle_deadline).await;
+
+                if self.get_idle_deadline() <= self.clock.now() {
+                    return;
+                }
+            }
+        };
+
+        let idle_timeout_expired = tokio::select! {
+            _ = wait_idle => true,
+            _ = stopped => false,
+        };
+
+        if idle_timeout_expired {
+            host_event!(info, "Idle timeout expired, stop application", idle_timeout = self.idle_timeout);
+            _ = self.is_running_sender.send(false);
+        }
+    }
+}
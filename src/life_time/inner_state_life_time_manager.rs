@@ -1,4 +1,3 @@
-use anthill_di::{Constructor, DependencyContext, types::BuildDependencyResult};
 use tokio::task::yield_now;
 use super::ILifeTimeManager;
 use std::sync::atomic::{AtomicBool, Ordering};
@@ -3,4 +3,9 @@ pub trait ILifeTimeManager : Sync + Send {
     async fn stop(&self);
     async fn is_running(&self) -> bool;
     async fn wait_for_stop(&self);
-}
\ No newline at end of file
+
+    /// Wait for forced stop request during shutdown, application skips to abort phase. By default never requested
+    async fn wait_for_abort(&self) {
+        std::future::pending::<()>().await
+    }
+}
@@ -5,4 +5,16 @@ mod ctrlc_life_time_manager;
 pub use ctrlc_life_time_manager::*;
 
 mod inner_state_life_time_manager;
-pub use inner_state_life_time_manager::*;
\ No newline at end of file
+pub use inner_state_life_time_manager::*;
+
+mod shutdown_signal;
+pub use shutdown_signal::*;
+
+mod composite_life_time_manager;
+pub use composite_life_time_manager::*;
+
+mod deadline_life_time_manager;
+pub use deadline_life_time_manager::*;
+
+mod idle_life_time_manager;
+pub use idle_life_time_manager::*;
@@ -0,0 +1,53 @@
+use tokio::sync::watch;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum ShutdownPhase {
+    Running,
+    /// Services should stop accepting new work and finish in-flight work, limited by ```CoreConfig::drain_timeout```
+    Draining,
+    /// Services ```on_stop``` called, limited by ```CoreConfig::on_stop_timeout```
+    Stopping,
+    /// Remaining service tasks aborted
+    Aborting,
+    Stopped,
+}
+
+/// Current application shutdown phase. Resolve as ```Arc<ShutdownSignal>```
+pub struct ShutdownSignal {
+    phase_sender: watch::Sender<ShutdownPhase>,
+    phase_receiver: watch::Receiver<ShutdownPhase>,
+}
+
+impl Default for ShutdownSignal {
+    fn default() -> Self {
+        let (phase_sender, phase_receiver) = watch::channel(ShutdownPhase::Running);
+        Self { phase_sender, phase_receiver }
+    }
+}
+
+impl ShutdownSignal {
+    pub fn get_phase(&self) -> ShutdownPhase {
+        *self.phase_receiver.borrow()
+    }
+
+    pub fn subscribe(&self) -> watch::Receiver<ShutdownPhase> {
+        self.phase_receiver.clone()
+    }
+
+    /// Wait until shutdown reach phase (or later phase)
+    pub async fn wait_for_phase(&self, phase: ShutdownPhase) {
+        let mut phase_receiver = self.phase_receiver.clone();
+
+        while *phase_receiver.borrow() < phase {
+            if phase_receiver.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    pub (crate) fn set_phase(&self, phase: ShutdownPhase) {
+        host_event!(info, "Shutdown phase changed", phase = phase);
+        // receiver stored in self, send can't fail
+        _ = self.phase_sender.send(phase);
+    }
+}
@@ -0,0 +1,57 @@
+use std::{sync::{Arc, RwLock}, time::Duration};
+
+pub const SERVICE_START_DURATION: &str = "anthill_service_start_duration_seconds";
+pub const SERVICE_STOP_DURATION: &str = "anthill_service_stop_duration_seconds";
+pub const SERVICE_STATE: &str = "anthill_service_state";
+pub const SERVICE_RESTARTS: &str = "anthill_service_restarts_total";
+pub const SUPERVISOR_RESTARTS: &str = "anthill_supervisor_restarts_total";
+pub const BACKGROUND_SERVICE_PANICS: &str = "anthill_background_service_panics_total";
+pub const SERVICE_STALLS: &str = "anthill_service_stalls_total";
+pub const WAIT_FOR_STOP_DURATION: &str = "anthill_wait_for_stop_duration_seconds";
+
+pub type MetricLabels<'a> = &'a [(&'a str, &'a str)];
+
+/// Metrics backend, built-in ```MetricsRegistry``` used by default
+pub trait IMetricsRecorder: Sync + Send + 'static {
+    fn increment_counter(&self, name: &str, labels: MetricLabels, value: u64);
+    fn set_gauge(&self, name: &str, labels: MetricLabels, value: f64);
+    /// Remove gauge series of removed object, for example removed service
+    fn remove_gauge(&self, name: &str, labels: MetricLabels);
+    fn record_duration(&self, name: &str, labels: MetricLabels, duration: Duration);
+}
+
+/// Host metrics facade. Resolve as ```Arc<Metrics>```
+pub struct Metrics {
+    recorder: RwLock<Arc<dyn IMetricsRecorder>>,
+}
+
+impl Metrics {
+    pub fn new(recorder: Arc<dyn IMetricsRecorder>) -> Self {
+        Self { recorder: RwLock::new(recorder) }
+    }
+
+    /// Replace recorder, for example to forward host metrics into external metrics system
+    pub fn set_recorder<TRecorder: IMetricsRecorder>(&self, recorder: TRecorder) {
+        *self.recorder.write().unwrap() = Arc::new(recorder);
+    }
+
+    pub fn increment_counter(&self, name: &str, labels: MetricLabels, value: u64) {
+        self.get_recorder().increment_counter(name, labels, value)
+    }
+
+    pub fn set_gauge(&self, name: &str, labels: MetricLabels, value: f64) {
+        self.get_recorder().set_gauge(name, labels, value)
+    }
+
+    pub fn remove_gauge(&self, name: &str, labels: MetricLabels) {
+        self.get_recorder().remove_gauge(name, labels)
+    }
+
+    pub fn record_duration(&self, name: &str, labels: MetricLabels, duration: Duration) {
+        self.get_recorder().record_duration(name, labels, duration)
+    }
+
+    fn get_recorder(&self) -> Arc<dyn IMetricsRecorder> {
+        self.recorder.read().unwrap().clone()
+    }
+}
@@ -0,0 +1,113 @@
+use std::{collections::BTreeMap, fmt::Write, path::Path, sync::Mutex, time::Duration};
+
+use super::{IMetricsRecorder, MetricLabels};
+
+#[derive(Default)]
+struct Summary {
+    count: u64,
+    sum: f64,
+}
+
+#[derive(Default)]
+struct MetricsState {
+    counters: BTreeMap<String, BTreeMap<String, u64>>,
+    gauges: BTreeMap<String, BTreeMap<String, f64>>,
+    summaries: BTreeMap<String, BTreeMap<String, Summary>>,
+}
+
+/// In-process metrics registry, render in Prometheus text format. Resolve as ```Arc<MetricsRegistry>```
+#[derive(Default)]
+pub struct MetricsRegistry {
+    state: Mutex<MetricsState>,
+}
+
+impl MetricsRegistry {
+    pub fn get_counter(&self, name: &str, labels: MetricLabels) -> Option<u64> {
+        self.state.lock().unwrap().counters.get(name)?.get(&format_labels(labels)).cloned()
+    }
+
+    pub fn get_gauge(&self, name: &str, labels: MetricLabels) -> Option<f64> {
+        self.state.lock().unwrap().gauges.get(name)?.get(&format_labels(labels)).cloned()
+    }
+
+    /// Recorded durations count
+    pub fn get_duration_count(&self, name: &str, labels: MetricLabels) -> Option<u64> {
+        self.state.lock().unwrap().summaries.get(name)?.get(&format_labels(labels)).map(|summary| summary.count)
+    }
+
+    pub fn render_prometheus(&self) -> String {
+        let state = self.state.lock().unwrap();
+        let mut output = String::new();
+
+        for (name, series) in state.counters.iter() {
+            _ = writeln!(output, "# TYPE {name} counter");
+            for (labels, value) in series.iter() {
+                _ = writeln!(output, "{name}{labels} {value}");
+            }
+        }
+
+        for (name, series) in state.gauges.iter() {
+            _ = writeln!(output, "# TYPE {name} gauge");
+            for (labels, value) in series.iter() {
+                _ = writeln!(output, "{name}{labels} {value}");
+            }
+        }
+
+        for (name, series) in state.summaries.iter() {
+            _ = writeln!(output, "# TYPE {name} summary");
+            for (labels, summary) in series.iter() {
+                _ = writeln!(output, "{name}_sum{labels} {sum}", sum = summary.sum);
+                _ = writeln!(output, "{name}_count{labels} {count}", count = summary.count);
+            }
+        }
+
+        output
+    }
+
+    /// Write Prometheus text format to file, for example for node exporter textfile collector
+    pub async fn write_prometheus(&self, path: impl AsRef<Path>) -> std::io::Result<()> {
+        let output = self.render_prometheus();
+        tokio::fs::write(path, output).await
+    }
+}
+
+impl IMetricsRecorder for MetricsRegistry {
+    fn increment_counter(&self, name: &str, labels: MetricLabels, value: u64) {
+        *self.state.lock().unwrap().counters.entry(name.to_string()).or_default().entry(format_labels(labels)).or_default() += value;
+    }
+
+    fn set_gauge(&self, name: &str, labels: MetricLabels, value: f64) {
+        self.state.lock().unwrap().gauges.entry(name.to_string()).or_default().insert(format_labels(labels), value);
+    }
+
+    fn remove_gauge(&self, name: &str, labels: MetricLabels) {
+        let mut state = self.state.lock().unwrap();
+
+        if let Some(series) = state.gauges.get_mut(name) {
+            series.remove(&format_labels(labels));
+
+            if series.is_empty() {
+                state.gauges.remove(name);
+            }
+        }
+    }
+
+    fn record_duration(&self, name: &str, labels: MetricLabels, duration: Duration) {
+        let mut state = self.state.lock().unwrap();
+        let summary = state.summaries.entry(name.to_string()).or_default().entry(format_labels(labels)).or_default();
+        summary.count += 1;
+        summary.sum += duration.as_secs_f64();
+    }
+}
+
+fn format_labels(labels: MetricLabels) -> String {
+    if labels.is_empty() {
+        return String::new();
+    }
+
+    let labels: Vec<String> = labels.iter()
+        .map(|(name, value)| format!("{name}=\"{value}\"", value = value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")))
+        .collect();
+
+    format!("{{{labels}}}", labels = labels.join(","))
+}
@@ -0,0 +1,5 @@
+mod metrics_recorder;
+pub use metrics_recorder::*;
+
+mod metrics_registry;
+pub use metrics_registry::*;
@@ -0,0 +1,83 @@
+use std::{
+    fs::{File, OpenOptions},
+    io::{Read, Seek, SeekFrom, Write},
+};
+
+use crate::types::{PidFileError, PidFileResult};
+
+/// Pid file with exclusive advisory lock (```flock```, unix only), second instance with same pid file fails with ```PidFileError::AlreadyLocked```.
+/// Lock released by OS if process dies, then left file considered stale and overwritten. File removed on release or drop.
+/// On other platforms file written without lock and second instance not detected
+pub struct PidFile {
+    path: String,
+    file: Option<File>,
+}
+
+impl PidFile {
+    pub fn acquire(path: &str) -> PidFileResult<Self> {
+        let map_io_error = |err: std::io::Error| PidFileError::IoError { pid_file: path.to_string(), message: err.to_string() };
+
+        // not truncated before lock, content belongs to running instance
+        let mut file = OpenOptions::new().read(true).write(true).create(true).truncate(false).open(path).map_err(map_io_error)?;
+
+        let mut content = String::new();
+        file.read_to_string(&mut content).map_err(map_io_error)?;
+        let stored_pid = content.trim().parse::<u32>().ok();
+
+        if !try_lock(&file) {
+            return Err(PidFileError::AlreadyLocked { pid_file: path.to_string(), pid: stored_pid });
+        }
+
+        let pid = std::process::id();
+
+        if let Some(stored_pid) = stored_pid.filter(|stored_pid| *stored_pid != pid) {
+            host_event!(warn, "Stale pid file cleaned", pid_file = path, stored_pid = stored_pid);
+        }
+
+        file.set_len(0).map_err(map_io_error)?;
+        file.seek(SeekFrom::Start(0)).map_err(map_io_error)?;
+        writeln!(file, "{pid}").map_err(map_io_error)?;
+        file.sync_all().map_err(map_io_error)?;
+
+        host_event!(info, "Pid file created", pid_file = path, pid = pid);
+
+        Ok(Self { path: path.to_string(), file: Some(file) })
+    }
+
+    pub fn get_path(&self) -> &str {
+        &self.path
+    }
+
+    /// Remove file, then release lock
+    pub fn release(&mut self) {
+        if let Some(file) = self.file.take() {
+            if let Err(err) = std::fs::remove_file(&self.path) {
+                host_event!(error, "Pid file remove error", pid_file = self.path, error = err);
+            }
+
+            drop(file);
+            host_event!(info, "Pid file removed", pid_file = self.path);
+        }
+    }
+}
+
+impl Drop for PidFile {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+#[cfg(unix)]
+fn try_lock(file: &File) -> bool {
+    use std::os::unix::io::AsRawFd;
+
+    // SAFETY: valid descriptor owned by file, lock released when file closed
+    unsafe { libc::flock(file.as_raw_fd(), libc::LOCK_EX | libc::LOCK_NB) == 0 }
+}
+
+/// Lock not supported, file written but other instance not detected
+#[cfg(not(unix))]
+fn try_lock(_file: &File) -> bool {
+    host_event!(warn, "Pid file lock not supported on this platform, other instance not detected");
+    true
+}
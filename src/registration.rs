@@ -0,0 +1,54 @@
+use std::{future::Future, pin::Pin};
+
+use anthill_di::Constructor;
+
+use crate::{
+    services::{IBaseService, IBackgroundService, BackgroundService},
+    types::{AutoRegistrationError, AutoRegistrationResult},
+    Application,
+    IStartup,
+};
+
+pub type AutoRegistrationFuture<'a> = Pin<Box<dyn Future<Output = AutoRegistrationResult> + Send + 'a>>;
+
+/// Application configuration called by ```#[main]``` entry point before run
+pub type ConfigureApplicationFuture<'a> = Pin<Box<dyn Future<Output = ()> + Send + 'a>>;
+
+/// Type registration collected at link time from ```#[service]```, ```#[background_service]``` and ```#[startup]``` attributes
+pub struct AutoRegistration {
+    pub type_name: fn() -> &'static str,
+    pub register: for<'a> fn(&'a mut Application) -> AutoRegistrationFuture<'a>,
+}
+
+inventory::collect!(AutoRegistration);
+
+impl AutoRegistration {
+    pub const fn service<TService: IBaseService + Constructor>() -> Self {
+        Self { type_name: std::any::type_name::<TService>, register: register_service::<TService> }
+    }
+
+    pub const fn background_service<TService: IBackgroundService + Constructor>() -> Self {
+        Self { type_name: std::any::type_name::<TService>, register: register_service::<BackgroundService<TService>> }
+    }
+
+    pub const fn startup<TStartup: IStartup + Constructor>() -> Self {
+        Self { type_name: std::any::type_name::<TStartup>, register: register_startup::<TStartup> }
+    }
+
+    /// All registrations linked into binary, order is not specified
+    pub fn iter() -> impl Iterator<Item = &'static AutoRegistration> {
+        inventory::iter::<AutoRegistration>.into_iter()
+    }
+}
+
+fn register_service<TService: IBaseService + Constructor>(app: &mut Application) -> AutoRegistrationFuture<'_> {
+    Box::pin(async move {
+        app.register_service::<TService>().await.map_err(AutoRegistrationError::AddServiceError)
+    })
+}
+
+fn register_startup<TStartup: IStartup + Constructor>(app: &mut Application) -> AutoRegistrationFuture<'_> {
+    Box::pin(async move {
+        app.register_startup::<TStartup>().await.map_err(AutoRegistrationError::AddStartupError)
+    })
+}
@@ -1,16 +1,28 @@
-use anthill_di::types::{BuildDependencyResult, BuildDependencyError};
+use anthill_di::types::{BuildDependencyResult, BuildDependencyError, AddDependencyError, TypeInfo};
 use anthill_di::{Constructor, DependencyContext, DependencyLifeCycle};
-use std::sync::Arc;
+use std::{future::Future, sync::Arc};
 
-use tokio::sync::RwLock;
-use tokio::task::JoinHandle;
+use tokio::sync::{RwLock, oneshot};
+use tokio::task::{JoinHandle, AbortHandle};
 
-use super::IBaseService;
+use crate::metrics::{Metrics, BACKGROUND_SERVICE_PANICS};
+use crate::trace::{in_service_span, ServicePhase};
 
-/// You cant create 'mut self' method, because while service work, execute call with read lock
+use super::{IBaseService, ServiceFailure, ServiceId, CompletionTracker, ExecutionStatus};
+
+/// You cant create 'mut self' method, because while service work, execute call with read lock.
+/// Use ```IOwnedBackgroundService``` with ```OwnedBackgroundService``` for mutable state
 #[async_trait_with_sync::async_trait(Sync)]
 pub trait IBackgroundService where Self: Send + Sync + 'static {
     async fn execute(&self);
+
+    /// Called when background service added to service manager, see ```IBaseService::on_added```
+    fn on_added(&self, _service_id: ServiceId) {}
+
+    /// Exit code reported after execute returns in run to completion mode
+    fn get_exit_code(&self) -> i32 {
+        0
+    }
 }
 
 enum BackgroundServiceState {
@@ -21,18 +33,30 @@ enum BackgroundServiceState {
 pub struct BackgroundService<TService> where TService: IBackgroundService + Constructor {
     pub service: Arc<RwLock<TService>>,
     state: BackgroundServiceState,
+    failure_receiver: Option<oneshot::Receiver<ServiceFailure>>,
+    abort_handles: Vec<AbortHandle>,
+    completion_tracker: Arc<CompletionTracker>,
+    metrics: Arc<Metrics>,
 }
 
 #[async_trait_with_sync::async_trait(Sync)]
 impl<TService> Constructor for BackgroundService<TService> where TService: IBackgroundService + Constructor {
     async fn ctor(ctx: DependencyContext) ->  BuildDependencyResult<Self> {
-        ctx.register_type::<RwLock<TService>>(DependencyLifeCycle::Singleton).await
-            .map_err(|e| BuildDependencyError::AddDependencyError{err: e})?
-            .map_as::<RwLock<dyn IBackgroundService>>().await.map_err(|e| BuildDependencyError::Custom { message: format!("{e:?}").to_string() })?;
+        // Service already registered if background service created again (restart by supervisor or dynamic add), reuse singleton
+        match ctx.register_type::<RwLock<TService>>(DependencyLifeCycle::Singleton).await {
+            Ok(builder) => {
+                builder.map_as::<RwLock<dyn IBackgroundService>>().await.map_err(|e| BuildDependencyError::Custom { message: format!("{e:?}").to_string() })?;
+            },
+            Err(AddDependencyError::DependencyExist { .. }) => {},
+        }
 
         Ok(Self {
             service: ctx.resolve().await?,
             state: BackgroundServiceState::Pending,
+            failure_receiver: None,
+            abort_handles: Vec::new(),
+            completion_tracker: ctx.resolve().await?,
+            metrics: ctx.resolve().await?,
         })
     }
 }
@@ -41,15 +65,72 @@ impl<TService> Constructor for BackgroundService<TService> where TService: IBack
 impl<TService> IBaseService for BackgroundService<TService> where TService: IBackgroundService + Constructor {
     async fn on_start(&mut self) {
         let service_ref = self.service.clone();
-        
-        self.state = BackgroundServiceState::Started{work_task: tokio::spawn(async move {
-            service_ref.read().await.execute().await;
-        })}; 
+
+        let execute = async move {
+            let service_read_guard = service_ref.read().await;
+            service_read_guard.execute().await;
+            service_read_guard.get_exit_code()
+        };
+
+        let (work_task, failure_receiver, abort_handles) = spawn_tracked_execute(execute, TypeInfo::from_type::<TService>(), self.completion_tracker.clone(), self.metrics.clone());
+
+        self.failure_receiver = Some(failure_receiver);
+        self.abort_handles = abort_handles;
+        self.state = BackgroundServiceState::Started{ work_task };
+    }
+
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.service.read().await.on_added(service_id);
     }
 
     async fn on_stop(&mut self) {
-        if let BackgroundServiceState::Started{ work_task} = &mut self.state {
-            work_task.await.unwrap();
+        if let BackgroundServiceState::Started{ work_task} = std::mem::replace(&mut self.state, BackgroundServiceState::Pending) {
+            if let Err(err) = work_task.await {
+                host_event!(error, "Background service work task error", service = TypeInfo::from_type::<TService>().type_name, error = err);
+            }
         }
     }
-}
\ No newline at end of file
+
+    fn take_failure_receiver(&mut self) -> Option<oneshot::Receiver<ServiceFailure>> {
+        self.failure_receiver.take()
+    }
+
+    fn take_abort_handles(&mut self) -> Vec<AbortHandle> {
+        std::mem::take(&mut self.abort_handles)
+    }
+}
+
+/// Spawn execute tracked by completion tracker, panic reported as service failure.
+/// Return work task, failure receiver and abort handles of execute and work tasks
+pub (crate) fn spawn_tracked_execute<TExecute>(execute: TExecute, service_type_info: TypeInfo, completion_tracker: Arc<CompletionTracker>, metrics: Arc<Metrics>) -> (JoinHandle<()>, oneshot::Receiver<ServiceFailure>, Vec<AbortHandle>)
+where
+    TExecute: Future<Output = i32> + Send + 'static
+{
+    let (failure_sender, failure_receiver) = oneshot::channel();
+
+    completion_tracker.on_execute_started(&service_type_info);
+
+    let execute_task = tokio::spawn(in_service_span(execute, &service_type_info, ServicePhase::Execute));
+    let execute_abort_handle = execute_task.abort_handle();
+
+    let work_task = tokio::spawn(async move {
+        let status = match execute_task.await {
+            Ok(exit_code) => ExecutionStatus::Completed { exit_code },
+            Err(err) if err.is_panic() => {
+                host_event!(error, "Background service execute failed", service = service_type_info.type_name, error = err);
+                metrics.increment_counter(BACKGROUND_SERVICE_PANICS, &[("service", &service_type_info.type_name)], 1);
+
+                _ = failure_sender.send(ServiceFailure { service_type_info: service_type_info.clone(), message: err.to_string() });
+                ExecutionStatus::Panicked { message: err.to_string() }
+            },
+            // cancelled execute (on abort) is not failure
+            Err(_) => ExecutionStatus::Cancelled,
+        };
+
+        completion_tracker.on_execute_finished(service_type_info, status);
+    });
+
+    let abort_handles = vec![execute_abort_handle, work_task.abort_handle()];
+
+    (work_task, failure_receiver, abort_handles)
+}
@@ -1,11 +1,37 @@
 use anthill_di::types::TypeInfo;
+use tokio::{sync::oneshot, task::AbortHandle};
+
+use super::ServiceId;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceFailure {
+    pub service_type_info: TypeInfo,
+    pub message: String,
+}
 
 #[async_trait_with_sync::async_trait(Sync)]
 pub trait IBaseService where Self: Sync + Send + 'static {
     async fn on_start(&mut self);
     async fn on_stop(&mut self);
 
+    /// First shutdown phase: stop accepting new work and finish in-flight work before ```on_stop```
+    async fn on_drain(&mut self) {}
+
+    /// Called once when service added to service manager (for supervisor child before every start of new instance),
+    /// bind ```TaskSpawner``` and ```Heartbeat``` created in constructor to service here
+    async fn on_added(&mut self, _service_id: ServiceId) {}
+
     fn get_type_info(&self) -> TypeInfo {
         TypeInfo::from_type::<Self>()
     }
-}
\ No newline at end of file
+
+    /// Receiver notified if service fails after start. Taken by parent supervisor or service manager after each ```on_start```
+    fn take_failure_receiver(&mut self) -> Option<oneshot::Receiver<ServiceFailure>> {
+        None
+    }
+
+    /// Service tasks aborted if service not stopped in time. Taken by service manager after each ```on_start```
+    fn take_abort_handles(&mut self) -> Vec<AbortHandle> {
+        Vec::new()
+    }
+}
@@ -0,0 +1,210 @@
+use anthill_di::types::{BuildDependencyResult, TypeInfo};
+use anthill_di::{Constructor, DependencyContext, DependencyLifeCycle};
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::{Arc, Condvar, Mutex},
+    time::Duration,
+};
+
+use tokio::sync::{oneshot, watch};
+use tokio::task::{JoinHandle, AbortHandle};
+
+use crate::metrics::Metrics;
+
+use super::{IBaseService, ServiceFailure, ServiceId, CompletionTracker, spawn_tracked_execute};
+
+/// Stop request visible from blocking and async code, set on service stop or abort
+#[derive(Clone, Default)]
+pub struct StopToken {
+    state: Arc<(Mutex<bool>, Condvar)>,
+    stop_sender: Arc<watch::Sender<bool>>,
+}
+
+impl StopToken {
+    pub fn is_stop_requested(&self) -> bool {
+        *self.state.0.lock().unwrap()
+    }
+
+    /// Block current thread until stop requested or timeout expired, return ```true``` if stop requested
+    pub fn wait_timeout(&self, timeout: Duration) -> bool {
+        let (stop_requested, condvar) = &*self.state;
+        let stop_requested = condvar.wait_timeout_while(stop_requested.lock().unwrap(), timeout, |stop_requested| !*stop_requested).unwrap().0;
+        *stop_requested
+    }
+
+    /// Wait until stop requested, for async execute
+    pub async fn wait_stop(&self) {
+        let mut stop_receiver = self.stop_sender.subscribe();
+
+        // error only if sender dropped, token holds it
+        _ = stop_receiver.wait_for(|stop_requested| *stop_requested).await;
+    }
+
+    pub (crate) fn request_stop(&self) {
+        let (stop_requested, condvar) = &*self.state;
+        *stop_requested.lock().unwrap() = true;
+        condvar.notify_all();
+
+        self.stop_sender.send_replace(true);
+    }
+}
+
+/// Request stop when execute future dropped (aborted), blocking thread itself can't be aborted
+struct StopOnDrop(StopToken);
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.0.request_stop();
+    }
+}
+
+/// Blocking service executed on own named OS thread, execute should return when stop requested
+pub trait IBlockingService where Self: Send + Sync + 'static {
+    fn execute(&mut self, stop_token: &StopToken);
+
+    /// Called on service stop (in async context) with state returned from execute
+    fn on_stop(&mut self) {}
+
+    /// Called when service added to service manager and after instance rebuilt, see ```IBaseService::on_added```
+    fn on_added(&mut self, _service_id: ServiceId) {}
+
+    /// Exit code reported after execute returns in run to completion mode
+    fn get_exit_code(&self) -> i32 {
+        0
+    }
+
+    fn get_thread_name(&self) -> String {
+        std::any::type_name::<Self>().rsplit("::").next().unwrap_or_default().to_string()
+    }
+}
+
+/// Wrapper used to build service instance without registering it as singleton
+struct BlockingServiceInstance<TService>(TService);
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> Constructor for BlockingServiceInstance<TService> where TService: IBlockingService + Constructor {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self(TService::ctor(ctx).await?))
+    }
+}
+
+/// Run ```IBlockingService``` on dedicated thread, so blocking calls don't starve runtime.
+/// Service stop requests stop through ```StopToken``` and waits execute in stop timeout, thread panic reported as service failure.
+/// State lost if execute panics, then new instance built from ioc on next start (build error reported as execute panic)
+pub struct BlockingService<TService> where TService: IBlockingService + Constructor {
+    ctx: DependencyContext,
+    service: Option<TService>,
+    service_id: Option<ServiceId>,
+    stop_token: StopToken,
+    work_task: Option<(JoinHandle<()>, oneshot::Receiver<TService>)>,
+    failure_receiver: Option<oneshot::Receiver<ServiceFailure>>,
+    abort_handles: Vec<AbortHandle>,
+    completion_tracker: Arc<CompletionTracker>,
+    metrics: Arc<Metrics>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> Constructor for BlockingService<TService> where TService: IBlockingService + Constructor {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        // Error only if service already created before (restart by supervisor or dynamic add)
+        _ = ctx.register_type::<BlockingServiceInstance<TService>>(DependencyLifeCycle::Transient).await;
+
+        Ok(Self {
+            service: Some(ctx.resolve::<BlockingServiceInstance<TService>>().await?.0),
+            service_id: None,
+            stop_token: StopToken::default(),
+            work_task: None,
+            failure_receiver: None,
+            abort_handles: Vec::new(),
+            completion_tracker: ctx.resolve().await?,
+            metrics: ctx.resolve().await?,
+            ctx,
+        })
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> IBaseService for BlockingService<TService> where TService: IBlockingService + Constructor {
+    async fn on_start(&mut self) {
+        let service = match self.service.take() {
+            Some(service) => Ok(service),
+            None => self.ctx.resolve::<BlockingServiceInstance<TService>>().await.map(|service| {
+                let mut service = service.0;
+                if let Some(service_id) = self.service_id {
+                    service.on_added(service_id);
+                }
+                service
+            }),
+        };
+
+        self.stop_token = StopToken::default();
+        let stop_token = self.stop_token.clone();
+
+        let (state_sender, state_receiver) = oneshot::channel();
+
+        let execute = async move {
+            let mut service = service.unwrap_or_else(|err| panic!("Blocking service build error [{err:?}]"));
+            let stop_on_drop = StopOnDrop(stop_token.clone());
+
+            let (result_sender, result_receiver) = oneshot::channel();
+            std::thread::Builder::new()
+                .name(service.get_thread_name())
+                .spawn(move || {
+                    let result = panic::catch_unwind(AssertUnwindSafe(|| service.execute(&stop_token))).map(|_| service);
+                    _ = result_sender.send(result);
+                })
+                .unwrap_or_else(|err| panic!("Blocking service thread spawn error [{err:?}]"));
+
+            let service = match result_receiver.await.expect("Blocking service thread result lost") {
+                Ok(service) => service,
+                // report thread panic as execute panic
+                Err(payload) => panic::resume_unwind(payload),
+            };
+
+            drop(stop_on_drop);
+
+            let exit_code = service.get_exit_code();
+            _ = state_sender.send(service);
+
+            exit_code
+        };
+
+        let (work_task, failure_receiver, abort_handles) = spawn_tracked_execute(execute, TypeInfo::from_type::<TService>(), self.completion_tracker.clone(), self.metrics.clone());
+
+        self.failure_receiver = Some(failure_receiver);
+        self.abort_handles = abort_handles;
+        self.work_task = Some((work_task, state_receiver));
+    }
+
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.service_id = Some(service_id);
+
+        if let Some(service) = self.service.as_mut() {
+            service.on_added(service_id);
+        }
+    }
+
+    async fn on_stop(&mut self) {
+        self.stop_token.request_stop();
+
+        if let Some((work_task, state_receiver)) = self.work_task.take() {
+            if let Err(err) = work_task.await {
+                host_event!(error, "Blocking service work task error", service = TypeInfo::from_type::<TService>().type_name, error = err);
+            }
+
+            // state not returned if execute panicked
+            if let Ok(mut service) = state_receiver.await {
+                service.on_stop();
+                self.service = Some(service);
+            }
+        }
+    }
+
+    fn take_failure_receiver(&mut self) -> Option<oneshot::Receiver<ServiceFailure>> {
+        self.failure_receiver.take()
+    }
+
+    fn take_abort_handles(&mut self) -> Vec<AbortHandle> {
+        std::mem::take(&mut self.abort_handles)
+    }
+}
@@ -0,0 +1,147 @@
+use anthill_di::types::TypeInfo;
+use tokio::sync::watch;
+use std::sync::Mutex;
+
+/// Which background services application waits for in run to completion mode
+#[derive(Debug, Clone, PartialEq)]
+pub enum CompletionMode {
+    /// Stop when every background service execute completes
+    AllBackgroundServices,
+    /// Stop when listed background services (```TypeInfo::from_type::<TService>()```) execute completes
+    BackgroundServices(Vec<TypeInfo>),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecutionStatus {
+    /// Execute returned, exit code from ```IBackgroundService::get_exit_code```
+    Completed { exit_code: i32 },
+    Panicked { message: String },
+    /// Execute aborted during shutdown
+    Cancelled,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceCompletion {
+    pub service_type_info: TypeInfo,
+    pub status: ExecutionStatus,
+}
+
+/// Result of ```Application::run_to_completion```, completions in finish order
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct CompletionReport {
+    pub completions: Vec<ServiceCompletion>,
+}
+
+impl CompletionReport {
+    /// First non zero exit code, 1 if any execute panicked or cancelled, otherwise 0
+    pub fn get_exit_code(&self) -> i32 {
+        self.completions.iter()
+            .map(|completion| match completion.status {
+                ExecutionStatus::Completed { exit_code } => exit_code,
+                ExecutionStatus::Panicked { .. } | ExecutionStatus::Cancelled => 1,
+            })
+            .find(|exit_code| *exit_code != 0)
+            .unwrap_or(0)
+    }
+}
+
+#[derive(Default)]
+struct CompletionState {
+    mode: Option<CompletionMode>,
+    is_all_started: bool,
+    running_count: usize,
+    completions: Vec<ServiceCompletion>,
+}
+
+/// Collect background service execute results. Resolve as ```Arc<CompletionTracker>```
+pub struct CompletionTracker {
+    state: Mutex<CompletionState>,
+    changed_sender: watch::Sender<()>,
+    changed_receiver: watch::Receiver<()>,
+}
+
+impl Default for CompletionTracker {
+    fn default() -> Self {
+        let (changed_sender, changed_receiver) = watch::channel(());
+
+        Self {
+            state: Mutex::new(CompletionState::default()),
+            changed_sender,
+            changed_receiver,
+        }
+    }
+}
+
+impl CompletionTracker {
+    pub fn get_report(&self) -> CompletionReport {
+        CompletionReport { completions: self.state.lock().unwrap().completions.clone() }
+    }
+
+    pub (crate) fn set_mode(&self, mode: CompletionMode) {
+        self.state.lock().unwrap().mode = Some(mode);
+    }
+
+    /// Completion checked only after application start, so fast services can't stop application before others started
+    pub (crate) fn set_all_started(&self) {
+        self.state.lock().unwrap().is_all_started = true;
+        self.notify_changed();
+    }
+
+    pub (crate) fn on_execute_started(&self, service_type_info: &TypeInfo) {
+        let mut state = self.state.lock().unwrap();
+
+        if state.is_tracked(service_type_info) {
+            state.running_count += 1;
+        }
+    }
+
+    pub (crate) fn on_execute_finished(&self, service_type_info: TypeInfo, status: ExecutionStatus) {
+        let mut state = self.state.lock().unwrap();
+
+        if !state.is_tracked(&service_type_info) {
+            return;
+        }
+
+        host_event!(info, "Background service execute finished", service = service_type_info.type_name, status = status);
+
+        state.running_count -= 1;
+        state.completions.push(ServiceCompletion { service_type_info, status });
+        drop(state);
+
+        self.notify_changed();
+    }
+
+    /// Wait until all tracked executions completed, pending forever if run to completion mode disabled
+    pub (crate) async fn wait_completed(&self) {
+        let mut changed_receiver = self.changed_receiver.clone();
+
+        loop {
+            if self.state.lock().unwrap().is_completed() {
+                return;
+            }
+
+            if changed_receiver.changed().await.is_err() {
+                std::future::pending::<()>().await;
+            }
+        }
+    }
+
+    fn notify_changed(&self) {
+        // receiver stored in self, send can't fail
+        _ = self.changed_sender.send(());
+    }
+}
+
+impl CompletionState {
+    fn is_tracked(&self, service_type_info: &TypeInfo) -> bool {
+        match &self.mode {
+            None => false,
+            Some(CompletionMode::AllBackgroundServices) => true,
+            Some(CompletionMode::BackgroundServices(type_infos)) => type_infos.contains(service_type_info),
+        }
+    }
+
+    fn is_completed(&self) -> bool {
+        self.mode.is_some() && self.is_all_started && self.running_count == 0
+    }
+}
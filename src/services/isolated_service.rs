@@ -0,0 +1,123 @@
+use anthill_di::types::{BuildDependencyResult, BuildDependencyError};
+use anthill_di::{Constructor, DependencyContext, DependencyLifeCycle};
+use std::{future::Future, panic, sync::Arc};
+
+use tokio::{
+    runtime::{Handle, Runtime},
+    sync::{RwLock, oneshot},
+    task::AbortHandle,
+};
+
+use crate::configs::CoreConfigSnapshot;
+
+use super::{IBaseService, ServiceFailure, ServiceId};
+
+/// Wrapper used to build service instance without registering it as singleton
+struct IsolatedInstance<TService>(TService);
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> Constructor for IsolatedInstance<TService> where TService: IBaseService + Constructor {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self(TService::ctor(ctx).await?))
+    }
+}
+
+/// Runtime driven by own thread (current thread runtime runs tasks only inside ```block_on```), shut down when dropped
+struct IsolatedRuntime {
+    handle: Handle,
+    shutdown_sender: Option<oneshot::Sender<()>>,
+}
+
+impl IsolatedRuntime {
+    fn start(runtime: Runtime, thread_name: String) -> std::io::Result<Self> {
+        let handle = runtime.handle().clone();
+        let (shutdown_sender, shutdown_receiver) = oneshot::channel::<()>();
+
+        std::thread::Builder::new()
+            .name(thread_name)
+            .spawn(move || {
+                _ = runtime.block_on(shutdown_receiver);
+                // runtime dropped outside async context
+            })?;
+
+        Ok(Self { handle, shutdown_sender: Some(shutdown_sender) })
+    }
+}
+
+impl Drop for IsolatedRuntime {
+    fn drop(&mut self) {
+        if let Some(shutdown_sender) = self.shutdown_sender.take() {
+            _ = shutdown_sender.send(());
+        }
+    }
+}
+
+/// Run service lifecycle and all tasks spawned by it on own runtime (```CoreConfig::isolated_runtime```), so misbehaving service can't starve other services.
+/// Runtime shut down when service dropped, tasks still running are cancelled
+pub struct IsolatedService<TService> where TService: IBaseService + Constructor {
+    service: Arc<RwLock<TService>>,
+    runtime: IsolatedRuntime,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> Constructor for IsolatedService<TService> where TService: IBaseService + Constructor {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        // Error only if service already created before (restart by supervisor or dynamic add)
+        _ = ctx.register_type::<IsolatedInstance<TService>>(DependencyLifeCycle::Transient).await;
+
+        let core_config: Arc<RwLock<CoreConfigSnapshot>> = ctx.resolve().await?;
+        let mut runtime_config = core_config.read().await.value.isolated_runtime.clone();
+        let thread_name = runtime_config.thread_name.get_or_insert_with(|| std::any::type_name::<TService>().rsplit("::").next().unwrap_or_default().to_string()).clone();
+
+        let runtime = runtime_config.build()
+            .and_then(|runtime| IsolatedRuntime::start(runtime, thread_name))
+            .map_err(|e| BuildDependencyError::Custom { message: format!("Isolated runtime build error [{e:?}]") })?;
+
+        Ok(Self {
+            service: Arc::new(RwLock::new(ctx.resolve::<IsolatedInstance<TService>>().await?.0)),
+            runtime,
+        })
+    }
+}
+
+impl<TService> IsolatedService<TService> where TService: IBaseService + Constructor {
+    /// Run lifecycle call on isolated runtime, panic propagated to caller
+    async fn run_isolated<TOutput, TFuture>(&self, call: impl FnOnce(Arc<RwLock<TService>>) -> TFuture) -> Option<TOutput>
+    where
+        TFuture: Future<Output = TOutput> + Send + 'static,
+        TOutput: Send + 'static,
+    {
+        match self.runtime.handle.spawn(call(self.service.clone())).await {
+            Ok(output) => Some(output),
+            Err(err) if err.is_panic() => panic::resume_unwind(err.into_panic()),
+            Err(_) => None,
+        }
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> IBaseService for IsolatedService<TService> where TService: IBaseService + Constructor {
+    async fn on_start(&mut self) {
+        self.run_isolated(|service| async move { service.write().await.on_start().await }).await;
+    }
+
+    async fn on_drain(&mut self) {
+        self.run_isolated(|service| async move { service.write().await.on_drain().await }).await;
+    }
+
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.service.write().await.on_added(service_id).await;
+    }
+
+    async fn on_stop(&mut self) {
+        self.run_isolated(|service| async move { service.write().await.on_stop().await }).await;
+    }
+
+    fn take_failure_receiver(&mut self) -> Option<oneshot::Receiver<ServiceFailure>> {
+        self.service.try_write().ok()?.take_failure_receiver()
+    }
+
+    fn take_abort_handles(&mut self) -> Vec<AbortHandle> {
+        self.service.try_write().map(|mut service| service.take_abort_handles()).unwrap_or_default()
+    }
+}
@@ -2,4 +2,37 @@ mod base_service;
 pub use base_service::*;
 
 mod background_service;
-pub use background_service::*;
\ No newline at end of file
+pub use background_service::*;
+
+mod owned_background_service;
+pub use owned_background_service::*;
+
+mod blocking_service;
+pub use blocking_service::*;
+
+mod isolated_service;
+pub use isolated_service::*;
+
+mod scoped_background_service;
+pub use scoped_background_service::*;
+
+mod service_scope;
+pub use service_scope::*;
+
+mod process_service;
+pub use process_service::*;
+
+mod completion_tracker;
+pub use completion_tracker::*;
+
+mod service_manager;
+pub use service_manager::*;
+
+mod supervisor;
+pub use supervisor::*;
+
+mod stall_watchdog;
+pub use stall_watchdog::*;
+
+mod task_spawner;
+pub use task_spawner::*;
@@ -0,0 +1,152 @@
+use anthill_di::types::{BuildDependencyResult, TypeInfo};
+use anthill_di::{Constructor, DependencyContext, DependencyLifeCycle};
+use std::sync::Arc;
+
+use tokio::sync::oneshot;
+use tokio::task::{JoinHandle, AbortHandle};
+
+use crate::metrics::Metrics;
+
+use super::{IBaseService, ServiceFailure, ServiceId, CompletionTracker, StopToken, spawn_tracked_execute};
+
+/// Background service with owned state, execute moves service into work task without lock and should return when stop requested
+#[async_trait_with_sync::async_trait(Sync)]
+pub trait IOwnedBackgroundService where Self: Send + Sync + 'static {
+    async fn execute(&mut self, stop_token: &StopToken);
+
+    /// Called on service stop with state returned from execute
+    async fn on_stop(&mut self) {}
+
+    /// Called when service added to service manager and after instance rebuilt, see ```IBaseService::on_added```
+    fn on_added(&mut self, _service_id: ServiceId) {}
+
+    /// Exit code reported after execute returns in run to completion mode
+    fn get_exit_code(&self) -> i32 {
+        0
+    }
+}
+
+/// Wrapper used to build service instance without registering it as singleton
+struct OwnedService<TService>(TService);
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> Constructor for OwnedService<TService> where TService: IOwnedBackgroundService + Constructor {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self(TService::ctor(ctx).await?))
+    }
+}
+
+/// Service moved into work task on start and returned back on stop, stop requested through ```StopToken``` before waiting execute.
+/// State lost if execute panics or aborted, then new instance built from ioc on next start (build error reported as execute panic)
+pub struct OwnedBackgroundService<TService> where TService: IOwnedBackgroundService + Constructor {
+    ctx: DependencyContext,
+    service: Option<TService>,
+    service_id: Option<ServiceId>,
+    stop_token: StopToken,
+    work_task: Option<(JoinHandle<()>, oneshot::Receiver<TService>)>,
+    failure_receiver: Option<oneshot::Receiver<ServiceFailure>>,
+    abort_handles: Vec<AbortHandle>,
+    completion_tracker: Arc<CompletionTracker>,
+    metrics: Arc<Metrics>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> Constructor for OwnedBackgroundService<TService> where TService: IOwnedBackgroundService + Constructor {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        // Error only if service already created before (restart by supervisor or dynamic add)
+        _ = ctx.register_type::<OwnedService<TService>>(DependencyLifeCycle::Transient).await;
+
+        Ok(Self {
+            service: Some(ctx.resolve::<OwnedService<TService>>().await?.0),
+            service_id: None,
+            stop_token: StopToken::default(),
+            work_task: None,
+            failure_receiver: None,
+            abort_handles: Vec::new(),
+            completion_tracker: ctx.resolve().await?,
+            metrics: ctx.resolve().await?,
+            ctx,
+        })
+    }
+}
+
+impl<TService> OwnedBackgroundService<TService> where TService: IOwnedBackgroundService + Constructor {
+    /// Service state, ```None``` while execute running or after state lost
+    pub fn get_service(&self) -> Option<&TService> {
+        self.service.as_ref()
+    }
+
+    pub fn get_service_mut(&mut self) -> Option<&mut TService> {
+        self.service.as_mut()
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> IBaseService for OwnedBackgroundService<TService> where TService: IOwnedBackgroundService + Constructor {
+    async fn on_start(&mut self) {
+        let service = match self.service.take() {
+            Some(service) => Ok(service),
+            None => self.ctx.resolve::<OwnedService<TService>>().await.map(|service| {
+                let mut service = service.0;
+                if let Some(service_id) = self.service_id {
+                    service.on_added(service_id);
+                }
+                service
+            }),
+        };
+
+        self.stop_token = StopToken::default();
+        let stop_token = self.stop_token.clone();
+
+        let (state_sender, state_receiver) = oneshot::channel();
+
+        let execute = async move {
+            let mut service = service.unwrap_or_else(|err| panic!("Owned background service build error [{err:?}]"));
+
+            service.execute(&stop_token).await;
+            let exit_code = service.get_exit_code();
+
+            _ = state_sender.send(service);
+
+            exit_code
+        };
+
+        let (work_task, failure_receiver, abort_handles) = spawn_tracked_execute(execute, TypeInfo::from_type::<TService>(), self.completion_tracker.clone(), self.metrics.clone());
+
+        self.failure_receiver = Some(failure_receiver);
+        self.abort_handles = abort_handles;
+        self.work_task = Some((work_task, state_receiver));
+    }
+
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.service_id = Some(service_id);
+
+        if let Some(service) = self.service.as_mut() {
+            service.on_added(service_id);
+        }
+    }
+
+    async fn on_stop(&mut self) {
+        self.stop_token.request_stop();
+
+        if let Some((work_task, state_receiver)) = self.work_task.take() {
+            if let Err(err) = work_task.await {
+                host_event!(error, "Background service work task error", service = TypeInfo::from_type::<TService>().type_name, error = err);
+            }
+
+            // state not returned if execute panicked
+            if let Ok(mut service) = state_receiver.await {
+                service.on_stop().await;
+                self.service = Some(service);
+            }
+        }
+    }
+
+    fn take_failure_receiver(&mut self) -> Option<oneshot::Receiver<ServiceFailure>> {
+        self.failure_receiver.take()
+    }
+
+    fn take_abort_handles(&mut self) -> Vec<AbortHandle> {
+        std::mem::take(&mut self.abort_handles)
+    }
+}
@@ -0,0 +1,276 @@
+use std::{any::TypeId, process::{ExitStatus, Stdio}, sync::{Arc, Weak}};
+
+use anthill_di::types::TypeInfo;
+
+use tokio::{
+    io::{AsyncBufReadExt, AsyncRead, BufReader},
+    process::{Child, Command},
+    sync::{oneshot, watch},
+    task::{JoinHandle, AbortHandle},
+};
+
+use crate::{
+    configs::{ProcessConfig, ProcessRestartPolicy},
+    events::{HostEvent, HostEventBus},
+    time::Clock,
+};
+
+use super::{IBaseService, ServiceFailure, ServiceId, ServiceManager};
+
+/// Run external command from ```ProcessConfig```, restart it by policy and forward stdout/stderr to host log.
+/// On stop process receives SIGTERM (kill on non unix), then SIGKILL after ```ProcessConfig::stop_timeout```.
+/// Exit without restart with failure status reported as service failure, clean exit without restart stops service
+pub struct ProcessService {
+    config: ProcessConfig,
+    clock: Arc<Clock>,
+    event_bus: Arc<HostEventBus>,
+    service_manager: Weak<ServiceManager>,
+    service_id: Option<ServiceId>,
+    stop_sender: Option<watch::Sender<bool>>,
+    work_task: Option<JoinHandle<()>>,
+    failure_receiver: Option<oneshot::Receiver<ServiceFailure>>,
+    abort_handles: Vec<AbortHandle>,
+}
+
+impl ProcessService {
+    pub fn new(config: ProcessConfig, service_manager: &ServiceManager) -> Self {
+        Self {
+            config,
+            clock: service_manager.get_clock(),
+            event_bus: service_manager.get_event_bus(),
+            service_manager: service_manager.get_self_ref(),
+            service_id: None,
+            stop_sender: None,
+            work_task: None,
+            failure_receiver: None,
+            abort_handles: Vec::new(),
+        }
+    }
+
+    pub fn get_config(&self) -> &ProcessConfig {
+        &self.config
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for ProcessService {
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.service_id = Some(service_id);
+    }
+
+    async fn on_start(&mut self) {
+        let (stop_sender, stop_receiver) = watch::channel(false);
+        let (failure_sender, failure_receiver) = oneshot::channel();
+
+        let work_task = tokio::spawn(ProcessRunner {
+            config: self.config.clone(),
+            clock: self.clock.clone(),
+            event_bus: self.event_bus.clone(),
+            type_info: self.get_type_info(),
+            // supervisor child instance not added to service manager, clean exit ignored
+            service_manager: self.service_manager.clone(),
+            service_id: self.service_id,
+            stop_receiver,
+            failure_sender: Some(failure_sender),
+        }.run());
+
+        self.stop_sender = Some(stop_sender);
+        self.failure_receiver = Some(failure_receiver);
+        self.abort_handles = vec![work_task.abort_handle()];
+        self.work_task = Some(work_task);
+    }
+
+    async fn on_stop(&mut self) {
+        if let Some(stop_sender) = self.stop_sender.take() {
+            _ = stop_sender.send(true);
+        }
+
+        if let Some(work_task) = self.work_task.take() {
+            if let Err(err) = work_task.await {
+                host_event!(error, "Process service work task error", process = self.config.get_name(), error = err);
+            }
+        }
+    }
+
+    fn get_type_info(&self) -> TypeInfo {
+        TypeInfo::new(TypeId::of::<Self>(), format!("{type_name}[{name}]", type_name = std::any::type_name::<Self>(), name = self.config.get_name()))
+    }
+
+    fn take_failure_receiver(&mut self) -> Option<oneshot::Receiver<ServiceFailure>> {
+        self.failure_receiver.take()
+    }
+
+    fn take_abort_handles(&mut self) -> Vec<AbortHandle> {
+        std::mem::take(&mut self.abort_handles)
+    }
+}
+
+struct ProcessRunner {
+    config: ProcessConfig,
+    clock: Arc<Clock>,
+    event_bus: Arc<HostEventBus>,
+    type_info: TypeInfo,
+    service_manager: Weak<ServiceManager>,
+    service_id: Option<ServiceId>,
+    stop_receiver: watch::Receiver<bool>,
+    failure_sender: Option<oneshot::Sender<ServiceFailure>>,
+}
+
+impl ProcessRunner {
+    async fn run(mut self) {
+        let name = self.config.get_name().to_string();
+        let mut restarts = 0;
+
+        loop {
+            let mut child = match self.spawn() {
+                Ok(child) => child,
+                Err(err) => {
+                    host_event!(error, "Process spawn error", process = name, error = err);
+                    self.fail(format!("Process spawn error [{name}] [{err:?}]"));
+                    return;
+                },
+            };
+
+            host_event!(info, "Process started", process = name, pid = child.id());
+            self.event_bus.publish(HostEvent::ProcessStarted { name: name.clone(), pid: child.id() });
+
+            let stop_receiver = self.stop_receiver.clone();
+            let status = tokio::select! {
+                status = child.wait() => status,
+                _ = wait_stop(stop_receiver) => {
+                    let status = self.terminate(&mut child).await;
+                    self.publish_exited(&name, status.as_ref().ok(), false);
+                    return;
+                },
+            };
+
+            let status = match status {
+                Ok(status) => status,
+                Err(err) => {
+                    host_event!(error, "Process wait error", process = name, error = err);
+                    self.fail(format!("Process wait error [{name}] [{err:?}]"));
+                    return;
+                },
+            };
+
+            let restart = match self.config.restart_policy {
+                ProcessRestartPolicy::Never => false,
+                ProcessRestartPolicy::OnFailure => !status.success(),
+                ProcessRestartPolicy::Always => true,
+            } && self.config.max_restarts.map(|max_restarts| restarts < max_restarts).unwrap_or(true);
+
+            self.publish_exited(&name, Some(&status), restart);
+
+            if !restart {
+                if status.success() {
+                    self.finish().await;
+                } else {
+                    self.fail(format!("Process exited [{name}] [{status}]"));
+                }
+                return;
+            }
+
+            restarts += 1;
+
+            let stop_receiver = self.stop_receiver.clone();
+            tokio::select! {
+                _ = self.clock.sleep(self.config.restart_delay) => {},
+                _ = wait_stop(stop_receiver) => return,
+            }
+        }
+    }
+
+    fn spawn(&self) -> std::io::Result<Child> {
+        let mut command = Command::new(&self.config.program);
+        command.args(&self.config.args)
+            .envs(&self.config.env)
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            // process killed if work task aborted
+            .kill_on_drop(true);
+
+        if let Some(working_dir) = &self.config.working_dir {
+            command.current_dir(working_dir);
+        }
+
+        let mut child = command.spawn()?;
+
+        let name = self.config.get_name().to_string();
+        if let Some(stdout) = child.stdout.take() {
+            tokio::spawn(forward_output(stdout, name.clone(), false));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            tokio::spawn(forward_output(stderr, name, true));
+        }
+
+        Ok(child)
+    }
+
+    async fn terminate(&self, child: &mut Child) -> std::io::Result<ExitStatus> {
+        let name = self.config.get_name();
+        host_event!(info, "Stopping process ...", process = name, pid = child.id());
+
+        send_terminate(child);
+
+        if let Ok(status) = self.clock.timeout(self.config.stop_timeout, child.wait()).await {
+            return status;
+        }
+
+        host_event!(warn, "Process stop timeout expired, kill", process = name, timeout = self.config.stop_timeout);
+        child.kill().await?;
+        child.wait().await
+    }
+
+    fn publish_exited(&self, name: &str, status: Option<&ExitStatus>, restart: bool) {
+        let exit_code = status.and_then(|status| status.code());
+        host_event!(info, "Process exited", process = name, exit_code = exit_code, restart = restart);
+        self.event_bus.publish(HostEvent::ProcessExited { name: name.to_string(), exit_code, restart });
+    }
+
+    fn fail(&mut self, message: String) {
+        if let Some(failure_sender) = self.failure_sender.take() {
+            _ = failure_sender.send(ServiceFailure { service_type_info: self.type_info.clone(), message });
+        }
+    }
+
+    async fn finish(&self) {
+        if let (Some(service_id), Some(service_manager)) = (self.service_id, self.service_manager.upgrade()) {
+            service_manager.set_running_stopped(service_id).await;
+        }
+    }
+}
+
+async fn wait_stop(mut stop_receiver: watch::Receiver<bool>) {
+    while !*stop_receiver.borrow() {
+        if stop_receiver.changed().await.is_err() {
+            // service dropped without stop
+            std::future::pending::<()>().await;
+        }
+    }
+}
+
+#[cfg(unix)]
+fn send_terminate(child: &mut Child) {
+    if let Some(pid) = child.id() {
+        // SAFETY: pid of own not yet awaited child, signal only requests termination
+        unsafe { libc::kill(pid as libc::pid_t, libc::SIGTERM); }
+    }
+}
+
+#[cfg(not(unix))]
+fn send_terminate(child: &mut Child) {
+    _ = child.start_kill();
+}
+
+async fn forward_output(output: impl AsyncRead + Unpin, name: String, is_stderr: bool) {
+    let mut lines = BufReader::new(output).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if is_stderr {
+            host_event!(warn, "Process stderr", process = name, line = line);
+        } else {
+            host_event!(info, "Process stdout", process = name, line = line);
+        }
+    }
+}
@@ -0,0 +1,110 @@
+use anthill_di::types::{BuildDependencyResult, BuildDependencyError, TypeInfo};
+use anthill_di::{Constructor, DependencyContext, DependencyLifeCycle};
+use std::sync::Arc;
+
+use tokio::sync::oneshot;
+use tokio::task::{JoinHandle, AbortHandle};
+
+use crate::metrics::Metrics;
+
+use super::{IBaseService, IBackgroundService, ServiceFailure, ServiceId, CompletionTracker, ServiceScopeFactory, spawn_tracked_execute};
+
+/// Wrapper used to build service per execution without registering it as singleton
+struct ScopedExecution<TService>(TService);
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> Constructor for ScopedExecution<TService> where TService: IBackgroundService + Constructor {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self(TService::ctor(ctx).await?))
+    }
+}
+
+/// Background service built in fresh ```ServiceScope``` on every start, service and its scoped dependencies are dropped after execute.
+/// Build error reported as execute panic (service failure)
+pub struct ScopedBackgroundService<TService> where TService: IBackgroundService + Constructor {
+    scope_factory: Arc<ServiceScopeFactory>,
+    service_id: Option<ServiceId>,
+    work_task: Option<JoinHandle<()>>,
+    failure_receiver: Option<oneshot::Receiver<ServiceFailure>>,
+    abort_handles: Vec<AbortHandle>,
+    completion_tracker: Arc<CompletionTracker>,
+    metrics: Arc<Metrics>,
+    service_phantom_data: std::marker::PhantomData<TService>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> Constructor for ScopedBackgroundService<TService> where TService: IBackgroundService + Constructor {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        // Error only if service already created before (restart by supervisor or dynamic add)
+        _ = ctx.register_type::<ScopedExecution<TService>>(DependencyLifeCycle::Transient).await;
+
+        Ok(Self {
+            scope_factory: ctx.resolve().await?,
+            service_id: None,
+            work_task: None,
+            failure_receiver: None,
+            abort_handles: Vec::new(),
+            completion_tracker: ctx.resolve().await?,
+            metrics: ctx.resolve().await?,
+            service_phantom_data: std::marker::PhantomData,
+        })
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> IBaseService for ScopedBackgroundService<TService> where TService: IBackgroundService + Constructor {
+    async fn on_start(&mut self) {
+        let scope_factory = self.scope_factory.clone();
+        let service_id = self.service_id;
+
+        let execute = async move {
+            let build_result: BuildDependencyResult<_> = async {
+                let scope = scope_factory.create_scope().await?;
+                let service = scope.resolve::<ScopedExecution<TService>>().await?.0;
+                Ok::<_, BuildDependencyError>((scope, service))
+            }.await;
+
+            let (scope, service) = build_result.unwrap_or_else(|err| panic!("Scoped background service build error [{err:?}]"));
+
+            if let Some(service_id) = service_id {
+                service.on_added(service_id);
+            }
+
+            service.execute().await;
+            let exit_code = service.get_exit_code();
+
+            // service dropped before scope, then scoped dependencies
+            drop(service);
+            drop(scope);
+
+            exit_code
+        };
+
+        let (work_task, failure_receiver, abort_handles) = spawn_tracked_execute(execute, TypeInfo::from_type::<TService>(), self.completion_tracker.clone(), self.metrics.clone());
+
+        self.failure_receiver = Some(failure_receiver);
+        self.abort_handles = abort_handles;
+        self.work_task = Some(work_task);
+    }
+
+    /// Service built on every start, bound to service id after build
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.service_id = Some(service_id);
+    }
+
+    async fn on_stop(&mut self) {
+        if let Some(work_task) = self.work_task.take() {
+            if let Err(err) = work_task.await {
+                host_event!(error, "Background service work task error", service = TypeInfo::from_type::<TService>().type_name, error = err);
+            }
+        }
+    }
+
+    fn take_failure_receiver(&mut self) -> Option<oneshot::Receiver<ServiceFailure>> {
+        self.failure_receiver.take()
+    }
+
+    fn take_abort_handles(&mut self) -> Vec<AbortHandle> {
+        std::mem::take(&mut self.abort_handles)
+    }
+}
@@ -0,0 +1,646 @@
+use anthill_di::{
+    types::{BuildDependencyResult, TypeInfo},
+    Constructor,
+    DependencyContext,
+    DependencyLifeCycle,
+};
+use tokio::{sync::{RwLock, broadcast, oneshot}, task::{JoinHandle, AbortHandle}, };
+use std::{
+    collections::BTreeMap,
+    sync::{Arc, Mutex, OnceLock, Weak, atomic::{AtomicU64, Ordering}},
+    time::Duration,
+};
+
+use crate::{
+    configs::CoreConfigSnapshot,
+    time::Clock,
+    metrics::{Metrics, SERVICE_START_DURATION, SERVICE_STOP_DURATION, SERVICE_STATE, SERVICE_RESTARTS},
+    events::{HostEvent, HostEventBus, StopReason},
+    trace::{in_service_span, ServicePhase},
+    types::{ServiceManagerError, ServiceManagerResult},
+};
+
+use super::{IBaseService, ServiceFailure, TaskTracker};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ServiceId(u64);
+
+/// Taken from service after ```on_start```
+struct StartedService {
+    abort_handles: Vec<AbortHandle>,
+    failure_receiver: Option<oneshot::Receiver<ServiceFailure>>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ServiceState {
+    Pending,
+    Starting,
+    Running,
+    Stopping,
+    Stopped,
+    Failed,
+    Aborted,
+}
+
+impl ServiceState {
+    pub const ALL: [ServiceState; 7] = [
+        ServiceState::Pending,
+        ServiceState::Starting,
+        ServiceState::Running,
+        ServiceState::Stopping,
+        ServiceState::Stopped,
+        ServiceState::Failed,
+        ServiceState::Aborted,
+    ];
+}
+
+/// Published by ```ServiceManager``` on every service state change
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceStateChange {
+    pub service_id: ServiceId,
+    pub service_type_info: TypeInfo,
+    pub state: ServiceState,
+}
+
+struct ServiceEntry {
+    service: Arc<RwLock<dyn IBaseService>>,
+    type_info: TypeInfo,
+    state: ServiceState,
+    abort_handles: Vec<AbortHandle>,
+}
+
+/// Wrapper used to build dynamic services through ioc without mapping them into the application service collection
+struct DynamicService<TService>(TService);
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TService> Constructor for DynamicService<TService> where TService: IBaseService + Constructor {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self(TService::ctor(ctx).await?))
+    }
+}
+
+/// Owns every application service (registered before run and added at runtime) and drives their start and stop.
+/// Resolve as ```Arc<ServiceManager>```
+///
+/// Dynamic services created by ```add_service``` are built from the manager context, so they can't request ```Arc<ServiceManager>``` (cyclic reference)
+pub struct ServiceManager {
+    ctx: DependencyContext,
+    core_config: Arc<RwLock<CoreConfigSnapshot>>,
+    last_service_id: AtomicU64,
+    services: RwLock<BTreeMap<ServiceId, ServiceEntry>>,
+    state_sender: broadcast::Sender<ServiceStateChange>,
+    clock: Arc<Clock>,
+    metrics: Arc<Metrics>,
+    event_bus: Arc<HostEventBus>,
+    task_tracker: Arc<TaskTracker>,
+    escalated_failure: Mutex<Option<ServiceFailure>>,
+    stop_reason: Mutex<Option<StopReason>>,
+    self_ref: OnceLock<Weak<ServiceManager>>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for ServiceManager {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self {
+            core_config: ctx.resolve().await?,
+            last_service_id: AtomicU64::new(0),
+            services: RwLock::new(BTreeMap::new()),
+            state_sender: broadcast::channel(1024).0,
+            clock: ctx.resolve().await?,
+            metrics: ctx.resolve().await?,
+            event_bus: ctx.resolve().await?,
+            task_tracker: ctx.resolve().await?,
+            escalated_failure: Mutex::new(None),
+            stop_reason: Mutex::new(None),
+            self_ref: OnceLock::new(),
+            ctx,
+        })
+    }
+}
+
+impl ServiceManager {
+    /// Build service from ioc and add it in ```ServiceState::Pending``` state
+    pub async fn add_service<TService: IBaseService + Constructor>(&self) -> ServiceManagerResult<ServiceId> {
+        let service = self.build_service::<TService>().await?;
+        Ok(self.add_service_ref(service).await)
+    }
+
+    /// Add already created service in ```ServiceState::Pending``` state
+    pub async fn add_service_instance<TService: IBaseService>(&self, service: TService) -> ServiceId {
+        self.add_service_ref(Arc::new(RwLock::new(service))).await
+    }
+
+    /// Build new service instance from ioc without adding it to manager
+    pub (crate) async fn build_service<TService: IBaseService + Constructor>(&self) -> ServiceManagerResult<Arc<RwLock<dyn IBaseService>>> {
+        // Error only if service type already built before
+        _ = self.ctx.register_type::<DynamicService<TService>>(DependencyLifeCycle::Transient).await;
+
+        let service = self.ctx.resolve::<DynamicService<TService>>().await
+            .map_err(ServiceManagerError::IocBuildDependencyError)?;
+
+        Ok(Arc::new(RwLock::new(service.0)))
+    }
+
+    pub (crate) fn get_context(&self) -> &DependencyContext {
+        &self.ctx
+    }
+
+    pub (crate) fn get_clock(&self) -> Arc<Clock> {
+        self.clock.clone()
+    }
+
+    pub (crate) fn get_metrics(&self) -> Arc<Metrics> {
+        self.metrics.clone()
+    }
+
+    /// Set by application after resolve, used by failure watchers of started services
+    pub (crate) fn set_self_ref(self: &Arc<Self>) {
+        _ = self.self_ref.set(Arc::downgrade(self));
+    }
+
+    /// Weak reference for tasks of started services, service manager owns services
+    pub (crate) fn get_self_ref(&self) -> Weak<ServiceManager> {
+        self.self_ref.get().cloned().unwrap_or_default()
+    }
+
+    /// Failure escalated past top-level supervisor, first failure kept
+    pub (crate) fn set_escalated_failure(&self, failure: ServiceFailure) {
+        self.escalated_failure.lock().unwrap().get_or_insert(failure);
+    }
+
+    /// Returned from ```Application::run``` as ```AppRunError::ServiceFailed```
+    pub (crate) fn take_escalated_failure(&self) -> Option<ServiceFailure> {
+        self.escalated_failure.lock().unwrap().take()
+    }
+
+    /// Set by host component before it stops life time manager, first reason kept
+    pub (crate) fn set_stop_reason(&self, reason: StopReason) {
+        self.stop_reason.lock().unwrap().get_or_insert(reason);
+    }
+
+    /// Published with ```HostEvent::StopRequested```, ```StopReason::LifeTimeManager``` if not set
+    pub (crate) fn take_stop_reason(&self) -> Option<StopReason> {
+        self.stop_reason.lock().unwrap().take()
+    }
+
+    pub (crate) fn get_event_bus(&self) -> Arc<HostEventBus> {
+        self.event_bus.clone()
+    }
+
+    pub (crate) fn get_task_tracker(&self) -> Arc<TaskTracker> {
+        self.task_tracker.clone()
+    }
+
+    /// Id of service added to manager or service owned by other service (supervisor child)
+    pub (crate) fn create_service_id(&self) -> ServiceId {
+        ServiceId(self.last_service_id.fetch_add(1, Ordering::SeqCst))
+    }
+
+    pub (crate) async fn get_timeouts(&self) -> (Duration, Duration) {
+        let core_config_read_guard = self.core_config.read().await;
+        (core_config_read_guard.value.on_start_timeout, core_config_read_guard.value.on_stop_timeout)
+    }
+
+    pub (crate) async fn add_service_ref(&self, service: Arc<RwLock<dyn IBaseService>>) -> ServiceId {
+        let service_id = self.create_service_id();
+        let type_info = service.read().await.get_type_info();
+
+        host_event!(info, "Service added", service_id = service_id, service = type_info.type_name);
+
+        self.task_tracker.add_owner(service_id, &type_info);
+        service.write().await.on_added(service_id).await;
+
+        self.notify_state_changed(service_id, &type_info, ServiceState::Pending);
+        self.services.write().await.insert(service_id, ServiceEntry { service, type_info, state: ServiceState::Pending, abort_handles: Vec::new() });
+
+        service_id
+    }
+
+    pub async fn start_service(&self, service_id: ServiceId) -> ServiceManagerResult {
+        self.start_service_from(service_id, &[ServiceState::Pending, ServiceState::Stopped]).await
+    }
+
+    /// Stop service if running (aborted if stop timeout expired), then start it again. Failed and aborted services also started
+    pub async fn restart_service(&self, service_id: ServiceId) -> ServiceManagerResult {
+        if self.get_state(service_id).await? == ServiceState::Running {
+            if let Err(err) = self.stop_service(service_id).await {
+                host_event!(warn, "Service stop on restart error", service_id = service_id, error = err);
+            }
+        }
+
+        let type_info = self.get_type_info(service_id).await?;
+        self.metrics.increment_counter(SERVICE_RESTARTS, &[("service", type_info.type_name.as_str())], 1);
+
+        self.start_service_from(service_id, &[ServiceState::Pending, ServiceState::Stopped, ServiceState::Failed, ServiceState::Aborted]).await
+    }
+
+    async fn start_service_from(&self, service_id: ServiceId, expected: &[ServiceState]) -> ServiceManagerResult {
+        let (service, type_info) = self.switch_state(service_id, expected, ServiceState::Starting).await?;
+
+        let on_start_timeout = self.core_config.read().await.value.on_start_timeout;
+
+        host_event!(info, "Starting service ...", service = type_info.type_name);
+        let task = self.spawn_on_start(service_id, service, type_info.clone()).await;
+        self.wait_started(service_id, type_info, task, on_start_timeout).await
+    }
+
+    pub async fn stop_service(&self, service_id: ServiceId) -> ServiceManagerResult {
+        let (service, type_info) = self.switch_state(service_id, &[ServiceState::Running], ServiceState::Stopping).await?;
+
+        let on_stop_timeout = self.core_config.read().await.value.on_stop_timeout;
+
+        host_event!(info, "Stopping service ...", service = type_info.type_name);
+        let task = self.spawn_on_stop(service_id, service, type_info.clone()).await;
+        let result = self.wait_stopped(service_id, type_info, task, on_stop_timeout).await;
+
+        if result.is_err() {
+            self.abort_service(service_id).await;
+        }
+
+        result
+    }
+
+    /// Stop service (if running) and remove it from manager, starting or stopping service can't be removed
+    pub async fn remove_service(&self, service_id: ServiceId) -> ServiceManagerResult {
+        match self.get_state(service_id).await? {
+            ServiceState::Running => self.stop_service(service_id).await?,
+            state @ (ServiceState::Starting | ServiceState::Stopping) => return Err(ServiceManagerError::InvalidServiceState { service_id, state }),
+            _ => {},
+        }
+
+        let entry = self.services.write().await.remove(&service_id)
+            .ok_or(ServiceManagerError::ServiceNotFound { service_id })?;
+
+        self.task_tracker.remove_owner(service_id);
+
+        let service_id_label = service_id.0.to_string();
+        for gauge_state in ServiceState::ALL {
+            let state_label = format!("{gauge_state:?}");
+            self.metrics.remove_gauge(SERVICE_STATE, &[("service_id", service_id_label.as_str()), ("service", entry.type_info.type_name.as_str()), ("state", state_label.as_str())]);
+        }
+
+        host_event!(info, "Service removed", service_id = service_id, service = entry.type_info.type_name);
+
+        Ok(())
+    }
+
+    pub async fn get_state(&self, service_id: ServiceId) -> ServiceManagerResult<ServiceState> {
+        self.services.read().await.get(&service_id)
+            .map(|entry| entry.state)
+            .ok_or(ServiceManagerError::ServiceNotFound { service_id })
+    }
+
+    pub async fn get_type_info(&self, service_id: ServiceId) -> ServiceManagerResult<TypeInfo> {
+        self.services.read().await.get(&service_id)
+            .map(|entry| entry.type_info.clone())
+            .ok_or(ServiceManagerError::ServiceNotFound { service_id })
+    }
+
+    pub async fn get_service_ids(&self) -> Vec<ServiceId> {
+        self.services.read().await.keys().cloned().collect()
+    }
+
+    /// Receive state changes published after subscription
+    pub fn subscribe_state_changes(&self) -> broadcast::Receiver<ServiceStateChange> {
+        self.state_sender.subscribe()
+    }
+
+    /// Start all pending services concurrently, wait all and return first error
+    pub (crate) async fn start_all(&self) -> ServiceManagerResult {
+        let on_start_timeout = self.core_config.read().await.value.on_start_timeout;
+
+        let mut service_start_tasks = Vec::new();
+        for (service_id, service, type_info) in self.switch_all_states(ServiceState::Pending, ServiceState::Starting).await {
+            host_event!(info, "Starting service ...", service = type_info.type_name);
+            let task = self.spawn_on_start(service_id, service, type_info.clone()).await;
+            service_start_tasks.push((service_id, type_info, task));
+        }
+
+        let mut first_error = None;
+        for (service_id, type_info, task) in service_start_tasks.into_iter() {
+            if let Err(err) = self.wait_started(service_id, type_info, task, on_start_timeout).await {
+                first_error.get_or_insert(err);
+            }
+        }
+
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Drain all running services concurrently, drain not finished in time is not error
+    pub (crate) async fn drain_all(&self, drain_timeout: Duration) {
+        let deadline = self.clock.now() + drain_timeout;
+
+        let mut service_drain_tasks = Vec::new();
+        for (_, service, type_info) in self.get_all_in_state(ServiceState::Running).await {
+            host_event!(info, "Draining service ...", service = type_info.type_name);
+            let on_drain = async move {
+                service.write().await.on_drain().await;
+            };
+            service_drain_tasks.push((type_info.clone(), tokio::spawn(in_service_span(on_drain, &type_info, ServicePhase::Drain))));
+        }
+
+        for (type_info, task) in service_drain_tasks.into_iter() {
+            if self.clock.timeout_at(deadline, task).await.is_err() {
+                host_event!(warn, "Service drain timeout expired", service = type_info.type_name, timeout = drain_timeout);
+                continue;
+            }
+
+            host_event!(info, "Service drained", service = type_info.type_name);
+        }
+    }
+
+    /// Stop all running services concurrently, return first error
+    pub (crate) async fn stop_all(&self) -> ServiceManagerResult {
+        let on_stop_timeout = self.core_config.read().await.value.on_stop_timeout;
+
+        let mut service_stop_tasks = Vec::new();
+        for (service_id, service, type_info) in self.switch_all_states(ServiceState::Running, ServiceState::Stopping).await {
+            host_event!(info, "Stopping service ...", service = type_info.type_name);
+            let task = self.spawn_on_stop(service_id, service, type_info.clone()).await;
+            service_stop_tasks.push((service_id, type_info, task));
+        }
+
+        let mut first_error = None;
+        for (service_id, type_info, task) in service_stop_tasks.into_iter() {
+            if let Err(err) = self.wait_stopped(service_id, type_info, task, on_stop_timeout).await {
+                first_error.get_or_insert(err);
+            }
+        }
+
+        first_error.map_or(Ok(()), Err)
+    }
+
+    /// Abort tasks of all not stopped services
+    pub (crate) async fn abort_all(&self) {
+        for service_id in self.get_service_ids().await {
+            self.abort_service(service_id).await;
+        }
+    }
+
+    async fn abort_service(&self, service_id: ServiceId) {
+        let mut services_write_guard = self.services.write().await;
+
+        let Some(entry) = services_write_guard.get_mut(&service_id) else {
+            return;
+        };
+
+        if matches!(entry.state, ServiceState::Pending | ServiceState::Stopped | ServiceState::Aborted) {
+            return;
+        }
+
+        host_event!(warn, "Aborting service", service = entry.type_info.type_name, state = entry.state);
+
+        for abort_handle in entry.abort_handles.drain(..) {
+            abort_handle.abort();
+        }
+        self.task_tracker.abort_owner(service_id);
+
+        entry.state = ServiceState::Aborted;
+        self.notify_state_changed(service_id, &entry.type_info, ServiceState::Aborted);
+    }
+
+    /// In-flight lifecycle task aborted with service tasks on abort
+    async fn spawn_on_start(&self, service_id: ServiceId, service: Arc<RwLock<dyn IBaseService>>, type_info: TypeInfo) -> JoinHandle<StartedService> {
+        let clock = self.clock.clone();
+        let metrics = self.metrics.clone();
+
+        self.task_tracker.start_owner(service_id);
+
+        let span_type_info = type_info.clone();
+        let on_start = async move {
+            let started_at = clock.now();
+
+            let mut service_write_guard = service.write().await;
+            service_write_guard.on_start().await;
+
+            metrics.record_duration(SERVICE_START_DURATION, &[("service", &type_info.type_name)], clock.now() - started_at);
+
+            StartedService {
+                abort_handles: service_write_guard.take_abort_handles(),
+                failure_receiver: service_write_guard.take_failure_receiver(),
+            }
+        };
+
+        let task = tokio::spawn(in_service_span(on_start, &span_type_info, ServicePhase::Start));
+
+        self.add_abort_handle(service_id, task.abort_handle()).await;
+
+        task
+    }
+
+    /// In-flight lifecycle task aborted with service tasks on abort
+    async fn spawn_on_stop(&self, service_id: ServiceId, service: Arc<RwLock<dyn IBaseService>>, type_info: TypeInfo) -> JoinHandle<()> {
+        let clock = self.clock.clone();
+        let metrics = self.metrics.clone();
+
+        let span_type_info = type_info.clone();
+        let task_tracker = self.task_tracker.clone();
+        let on_stop = async move {
+            let started_at = clock.now();
+
+            // tracked tasks aborted on drop if stop timeout expired
+            let tracked_tasks = task_tracker.cancel_owner(service_id);
+            service.write().await.on_stop().await;
+
+            if let Some(tracked_tasks) = tracked_tasks {
+                tracked_tasks.wait().await;
+            }
+
+            metrics.record_duration(SERVICE_STOP_DURATION, &[("service", &type_info.type_name)], clock.now() - started_at);
+        };
+
+        let task = tokio::spawn(in_service_span(on_stop, &span_type_info, ServicePhase::Stop));
+
+        self.add_abort_handle(service_id, task.abort_handle()).await;
+
+        task
+    }
+
+    /// Start task aborted if timeout expired, service failed if start timeout expired or ```on_start``` panicked
+    async fn wait_started(&self, service_id: ServiceId, type_info: TypeInfo, task: JoinHandle<StartedService>, on_start_timeout: Duration) -> ServiceManagerResult {
+        let abort_handle = task.abort_handle();
+
+        let Ok(start_result) = self.clock.timeout(on_start_timeout, task).await else {
+            host_event!(error, "Service start error", service = type_info.type_name, timeout = on_start_timeout);
+            abort_handle.abort();
+            self.set_failed(service_id).await;
+
+            return Err(ServiceManagerError::ServiceStartTimeoutExpired { timeout_duration: on_start_timeout, service_type_info: type_info });
+        };
+
+        let started_service = match start_result {
+            Ok(started_service) => started_service,
+            Err(err) => {
+                host_event!(error, "Service start error", service = type_info.type_name, error = err);
+
+                // cancelled only by abort, service already aborted
+                if !err.is_cancelled() {
+                    self.set_failed(service_id).await;
+                }
+
+                return Err(ServiceManagerError::ServiceStartFailed { service_type_info: type_info, message: err.to_string() });
+            },
+        };
+
+        // failure watcher holds weak reference, service manager owns services
+        let service_manager = self.get_self_ref();
+
+        if let Some(entry) = self.services.write().await.get_mut(&service_id) {
+            entry.state = ServiceState::Running;
+            entry.abort_handles = started_service.abort_handles;
+
+            if let Some(failure_receiver) = started_service.failure_receiver {
+                entry.abort_handles.push(self.spawn_failure_watcher(service_id, failure_receiver, service_manager));
+            }
+
+            self.notify_state_changed(service_id, &type_info, ServiceState::Running);
+        }
+
+        host_event!(info, "Service started", service = type_info.type_name);
+
+        Ok(())
+    }
+
+    /// Service failed if stop timeout expired or ```on_stop``` panicked
+    async fn wait_stopped(&self, service_id: ServiceId, type_info: TypeInfo, task: JoinHandle<()>, on_stop_timeout: Duration) -> ServiceManagerResult {
+        let Ok(stop_result) = self.clock.timeout(on_stop_timeout, task).await else {
+            host_event!(error, "Service stop error", service = type_info.type_name, timeout = on_stop_timeout);
+            self.set_failed(service_id).await;
+
+            return Err(ServiceManagerError::ServiceStopTimeoutExpired { timeout_duration: on_stop_timeout, service_type_info: type_info });
+        };
+
+        if let Err(err) = stop_result {
+            host_event!(error, "Service stop error", service = type_info.type_name, error = err);
+
+            // cancelled only by abort, service already aborted
+            if !err.is_cancelled() {
+                self.set_failed(service_id).await;
+            }
+
+            return Err(ServiceManagerError::ServiceStopFailed { service_type_info: type_info, message: err.to_string() });
+        }
+
+        if let Some(entry) = self.services.write().await.get_mut(&service_id) {
+            entry.state = ServiceState::Stopped;
+            entry.abort_handles.clear();
+            self.notify_state_changed(service_id, &type_info, ServiceState::Stopped);
+        }
+
+        host_event!(info, "Service stopped", service = type_info.type_name);
+
+        Ok(())
+    }
+
+    /// Running service switched to ```ServiceState::Failed``` on reported failure (execute panic, process exit).
+    /// Watcher finished without failure when service run finished
+    fn spawn_failure_watcher(&self, service_id: ServiceId, failure_receiver: oneshot::Receiver<ServiceFailure>, service_manager: Weak<ServiceManager>) -> AbortHandle {
+        tokio::spawn(async move {
+            let (Ok(failure), Some(service_manager)) = (failure_receiver.await, service_manager.upgrade()) else {
+                return;
+            };
+
+            service_manager.set_running_failed(service_id, failure).await;
+        }).abort_handle()
+    }
+
+    /// Failure of stopping or already stopped service ignored
+    async fn set_running_failed(&self, service_id: ServiceId, failure: ServiceFailure) {
+        if let Some(entry) = self.services.write().await.get_mut(&service_id) {
+            if entry.state != ServiceState::Running {
+                return;
+            }
+
+            host_event!(error, "Service failed", service = entry.type_info.type_name, message = failure.message);
+
+            entry.state = ServiceState::Failed;
+            self.notify_state_changed(service_id, &entry.type_info, ServiceState::Failed);
+        }
+    }
+
+    /// Running service finished by itself without failure (process exited cleanly), stop of stopping or already stopped service ignored
+    pub (crate) async fn set_running_stopped(&self, service_id: ServiceId) {
+        if let Some(entry) = self.services.write().await.get_mut(&service_id) {
+            if entry.state != ServiceState::Running {
+                return;
+            }
+
+            host_event!(info, "Service finished", service = entry.type_info.type_name);
+
+            entry.state = ServiceState::Stopped;
+            entry.abort_handles.clear();
+            self.notify_state_changed(service_id, &entry.type_info, ServiceState::Stopped);
+        }
+    }
+
+    async fn set_failed(&self, service_id: ServiceId) {
+        if let Some(entry) = self.services.write().await.get_mut(&service_id) {
+            entry.state = ServiceState::Failed;
+            self.notify_state_changed(service_id, &entry.type_info, ServiceState::Failed);
+        }
+    }
+
+    async fn switch_state(&self, service_id: ServiceId, expected: &[ServiceState], new_state: ServiceState) -> ServiceManagerResult<(Arc<RwLock<dyn IBaseService>>, TypeInfo)> {
+        let mut services_write_guard = self.services.write().await;
+
+        let entry = services_write_guard.get_mut(&service_id)
+            .ok_or(ServiceManagerError::ServiceNotFound { service_id })?;
+
+        if !expected.contains(&entry.state) {
+            return Err(ServiceManagerError::InvalidServiceState { service_id, state: entry.state });
+        }
+
+        entry.state = new_state;
+        self.notify_state_changed(service_id, &entry.type_info, new_state);
+
+        Ok((entry.service.clone(), entry.type_info.clone()))
+    }
+
+    async fn switch_all_states(&self, expected: ServiceState, new_state: ServiceState) -> Vec<(ServiceId, Arc<RwLock<dyn IBaseService>>, TypeInfo)> {
+        self.services.write().await.iter_mut()
+            .filter(|(_, entry)| entry.state == expected)
+            .map(|(service_id, entry)| {
+                entry.state = new_state;
+                self.notify_state_changed(*service_id, &entry.type_info, new_state);
+                (*service_id, entry.service.clone(), entry.type_info.clone())
+            })
+            .collect()
+    }
+
+    fn notify_state_changed(&self, service_id: ServiceId, type_info: &TypeInfo, state: ServiceState) {
+        let service_id_label = service_id.0.to_string();
+
+        for gauge_state in ServiceState::ALL {
+            let state_label = format!("{gauge_state:?}");
+            let labels = [("service_id", service_id_label.as_str()), ("service", type_info.type_name.as_str()), ("state", state_label.as_str())];
+            self.metrics.set_gauge(SERVICE_STATE, &labels, if gauge_state == state { 1.0 } else { 0.0 });
+        }
+
+        // error only if nobody subscribed
+        _ = self.state_sender.send(ServiceStateChange { service_id, service_type_info: type_info.clone(), state });
+
+        let service_type_info = type_info.clone();
+        self.event_bus.publish(match state {
+            ServiceState::Pending => HostEvent::ServiceRegistered { service_id, service_type_info },
+            ServiceState::Starting => HostEvent::ServiceStarting { service_id, service_type_info },
+            ServiceState::Running => HostEvent::ServiceStarted { service_id, service_type_info },
+            ServiceState::Stopping => HostEvent::ServiceStopping { service_id, service_type_info },
+            ServiceState::Stopped => HostEvent::ServiceStopped { service_id, service_type_info },
+            ServiceState::Failed => HostEvent::ServiceFailed { service_id, service_type_info },
+            ServiceState::Aborted => HostEvent::ServiceAborted { service_id, service_type_info },
+        });
+    }
+
+    async fn add_abort_handle(&self, service_id: ServiceId, abort_handle: AbortHandle) {
+        if let Some(entry) = self.services.write().await.get_mut(&service_id) {
+            entry.abort_handles.push(abort_handle);
+        }
+    }
+
+    async fn get_all_in_state(&self, state: ServiceState) -> Vec<(ServiceId, Arc<RwLock<dyn IBaseService>>, TypeInfo)> {
+        self.services.read().await.iter()
+            .filter(|(_, entry)| entry.state == state)
+            .map(|(service_id, entry)| (*service_id, entry.service.clone(), entry.type_info.clone()))
+            .collect()
+    }
+}
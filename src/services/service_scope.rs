@@ -0,0 +1,46 @@
+use anthill_di::{types::BuildDependencyResult, Constructor, DependencyContext};
+
+/// Fresh ioc scope for single unit of work (execution, iteration, message).
+/// Scoped dependencies (resolved as ```Weak<T>```) are created once per scope and dropped with it.
+/// Resolve as ```ServiceScope``` (transient) or create with ```Arc<ServiceScopeFactory>```
+pub struct ServiceScope {
+    ctx: DependencyContext,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for ServiceScope {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        let mut ctx = ctx;
+        ctx.set_empty_scope();
+
+        Ok(Self { ctx })
+    }
+}
+
+impl ServiceScope {
+    pub async fn resolve<TService: Sync + Send + 'static>(&self) -> BuildDependencyResult<TService> {
+        self.ctx.resolve().await
+    }
+
+    pub fn get_context(&self) -> &DependencyContext {
+        &self.ctx
+    }
+}
+
+/// Create ```ServiceScope``` per unit of work, resolve as ```Arc<ServiceScopeFactory>```
+pub struct ServiceScopeFactory {
+    ctx: DependencyContext,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for ServiceScopeFactory {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self { ctx })
+    }
+}
+
+impl ServiceScopeFactory {
+    pub async fn create_scope(&self) -> BuildDependencyResult<ServiceScope> {
+        self.ctx.resolve().await
+    }
+}
@@ -0,0 +1,280 @@
+use anthill_di::{
+    types::{BuildDependencyResult, TypeInfo},
+    Constructor,
+    DependencyContext,
+};
+use tokio::{
+    task::{AbortHandle, JoinHandle, JoinSet},
+    time::Instant,
+};
+use std::{
+    sync::{Arc, Mutex, Weak, atomic::{AtomicBool, Ordering}},
+    time::Duration,
+};
+
+use crate::{
+    events::{HostEvent, HostEventBus, StopReason},
+    life_time::ILifeTimeManager,
+    metrics::{Metrics, SERVICE_STALLS},
+    time::Clock,
+};
+
+use super::{IBaseService, ServiceId, ServiceManager, ServiceState};
+
+/// What ```StallWatchdog``` does with stalled service, ```HostEvent::ServiceUnhealthy``` published in any case
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StallAction {
+    /// Only report unhealthy status
+    Report,
+    /// Restart service heartbeat bound to with ```ServiceManager::restart_service```
+    Restart,
+    /// Stop application with life time manager
+    StopApplication,
+}
+
+struct HeartbeatEntry {
+    service_id: Mutex<Option<ServiceId>>,
+    deadline: Duration,
+    action: StallAction,
+    last_beat: Mutex<Instant>,
+    is_unhealthy: AtomicBool,
+}
+
+impl HeartbeatEntry {
+    fn reset(&self, now: Instant) {
+        *self.last_beat.lock().unwrap() = now;
+    }
+
+    fn get_service_id(&self) -> Option<ServiceId> {
+        *self.service_id.lock().unwrap()
+    }
+}
+
+/// Heartbeat of service, call ```beat``` from work loop more often than deadline.
+/// Checked by watchdog after bound to service, forgotten when all clones dropped
+#[derive(Clone)]
+pub struct Heartbeat {
+    entry: Arc<HeartbeatEntry>,
+    clock: Arc<Clock>,
+}
+
+impl Heartbeat {
+    /// Bind heartbeat to owning service, call from ```IBaseService::on_added```
+    pub fn bind(&self, service_id: ServiceId) {
+        *self.entry.service_id.lock().unwrap() = Some(service_id);
+        self.beat();
+    }
+
+    pub fn get_service_id(&self) -> Option<ServiceId> {
+        self.entry.get_service_id()
+    }
+
+    pub fn beat(&self) {
+        self.entry.reset(self.clock.now());
+    }
+
+    pub fn get_last_beat(&self) -> Instant {
+        *self.entry.last_beat.lock().unwrap()
+    }
+
+    pub fn get_deadline(&self) -> Duration {
+        self.entry.deadline
+    }
+}
+
+/// Heartbeats checked by ```StallWatchdog```. Resolve as ```Arc<HeartbeatRegistry>```
+pub struct HeartbeatRegistry {
+    clock: Arc<Clock>,
+    entries: Mutex<Vec<Weak<HeartbeatEntry>>>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for HeartbeatRegistry {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self {
+            clock: ctx.resolve().await?,
+            entries: Mutex::new(Vec::new()),
+        })
+    }
+}
+
+impl HeartbeatRegistry {
+    /// Heartbeat not bound to service, bind it in ```IBaseService::on_added``` with ```Heartbeat::bind```.
+    /// Heartbeat of supervisor child is not checked (child is not added to service manager), child restarted by supervisor only on failure
+    pub fn register(&self, deadline: Duration, action: StallAction) -> Heartbeat {
+        host_event!(info, "Heartbeat registered", deadline = deadline, action = action);
+
+        let entry = Arc::new(HeartbeatEntry {
+            service_id: Mutex::new(None),
+            deadline,
+            action,
+            last_beat: Mutex::new(self.clock.now()),
+            is_unhealthy: AtomicBool::new(false),
+        });
+
+        self.entries.lock().unwrap().push(Arc::downgrade(&entry));
+
+        Heartbeat { entry, clock: self.clock.clone() }
+    }
+
+    /// Services reported unhealthy by last watchdog check
+    pub fn get_unhealthy(&self) -> Vec<ServiceId> {
+        self.get_entries().into_iter()
+            .filter(|entry| entry.is_unhealthy.load(Ordering::SeqCst))
+            .filter_map(|entry| entry.get_service_id())
+            .collect()
+    }
+
+    /// Live heartbeats, dropped removed
+    fn get_entries(&self) -> Vec<Arc<HeartbeatEntry>> {
+        let mut entries = self.entries.lock().unwrap();
+        entries.retain(|entry| entry.strong_count() > 0);
+        entries.iter().filter_map(Weak::upgrade).collect()
+    }
+}
+
+/// Service which checks heartbeats every ```check_interval``` and applies ```StallAction``` to services with heartbeat older than deadline.
+/// Time while bound service is not running is not counted. Register with ```Application::register_stall_watchdog```
+pub struct StallWatchdog {
+    check_interval: Duration,
+    service_manager: Weak<ServiceManager>,
+    work_task: Option<JoinHandle<()>>,
+    abort_handles: Vec<AbortHandle>,
+}
+
+impl StallWatchdog {
+    pub (crate) fn new(check_interval: Duration, service_manager: Weak<ServiceManager>) -> Self {
+        Self {
+            check_interval,
+            service_manager,
+            work_task: None,
+            abort_handles: Vec::new(),
+        }
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for StallWatchdog {
+    async fn on_start(&mut self) {
+        let service_manager = self.service_manager.upgrade().expect("ServiceManager dropped before watchdog start");
+        let ctx = service_manager.get_context();
+
+        let mut checker = StallChecker {
+            registry: ctx.resolve().await.expect("HeartbeatRegistry not found"),
+            life_time_manager: ctx.resolve().await.expect("LifeTimeManager not found"),
+            clock: service_manager.get_clock(),
+            metrics: service_manager.get_metrics(),
+            event_bus: service_manager.get_event_bus(),
+            service_manager: self.service_manager.clone(),
+            actions: JoinSet::new(),
+        };
+
+        let check_interval = self.check_interval;
+
+        // in-flight stall actions owned by checker, aborted with work task
+        let work_task = tokio::spawn(async move {
+            loop {
+                checker.clock.sleep(check_interval).await;
+                checker.check().await;
+            }
+        });
+
+        self.abort_handles = vec![work_task.abort_handle()];
+        self.work_task = Some(work_task);
+    }
+
+    async fn on_stop(&mut self) {
+        if let Some(work_task) = self.work_task.take() {
+            work_task.abort();
+        }
+    }
+
+    fn take_abort_handles(&mut self) -> Vec<AbortHandle> {
+        std::mem::take(&mut self.abort_handles)
+    }
+}
+
+struct StallChecker {
+    registry: Arc<HeartbeatRegistry>,
+    life_time_manager: Arc<dyn ILifeTimeManager>,
+    clock: Arc<Clock>,
+    metrics: Arc<Metrics>,
+    event_bus: Arc<HostEventBus>,
+    service_manager: Weak<ServiceManager>,
+    actions: JoinSet<()>,
+}
+
+impl StallChecker {
+    /// Actions run in own tasks, so restart of one service doesn't delay checks of others
+    async fn check(&mut self) {
+        while self.actions.try_join_next().is_some() {}
+
+        let Some(service_manager) = self.service_manager.upgrade() else {
+            return;
+        };
+
+        for entry in self.registry.get_entries() {
+            // not bound heartbeat is not checked
+            let Some(service_id) = entry.get_service_id() else {
+                continue;
+            };
+
+            let now = self.clock.now();
+
+            // service removed
+            let (Ok(state), Ok(type_info)) = (service_manager.get_state(service_id).await, service_manager.get_type_info(service_id).await) else {
+                continue;
+            };
+
+            if state != ServiceState::Running {
+                entry.reset(now);
+                continue;
+            }
+
+            let stalled_for = now.saturating_duration_since(*entry.last_beat.lock().unwrap());
+
+            if stalled_for <= entry.deadline {
+                if entry.is_unhealthy.swap(false, Ordering::SeqCst) {
+                    host_event!(info, "Service heartbeat restored", service_id = service_id, service = type_info.type_name);
+                    self.event_bus.publish(HostEvent::ServiceHealthy { service_id, service_type_info: type_info });
+                }
+
+                continue;
+            }
+
+            // action applied once per stall
+            if entry.is_unhealthy.swap(true, Ordering::SeqCst) {
+                continue;
+            }
+
+            host_event!(warn, "Service stalled", service_id = service_id, service = type_info.type_name, stalled_for = stalled_for, deadline = entry.deadline, action = entry.action);
+            self.metrics.increment_counter(SERVICE_STALLS, &[("service", &type_info.type_name)], 1);
+            self.event_bus.publish(HostEvent::ServiceUnhealthy { service_id, service_type_info: type_info.clone(), stalled_for });
+
+            match entry.action {
+                StallAction::Report => {},
+                StallAction::Restart => {
+                    self.actions.spawn(restart_stalled(service_manager.clone(), entry, service_id, type_info, self.clock.clone(), self.event_bus.clone()));
+                },
+                StallAction::StopApplication => {
+                    service_manager.set_stop_reason(StopReason::ServiceStalled { service_type_info: type_info });
+                    let life_time_manager = self.life_time_manager.clone();
+                    self.actions.spawn(async move { life_time_manager.stop().await; });
+                },
+            }
+        }
+    }
+}
+
+async fn restart_stalled(service_manager: Arc<ServiceManager>, entry: Arc<HeartbeatEntry>, service_id: ServiceId, type_info: TypeInfo, clock: Arc<Clock>, event_bus: Arc<HostEventBus>) {
+    if let Err(err) = service_manager.restart_service(service_id).await {
+        host_event!(error, "Stalled service restart error", service_id = service_id, service = type_info.type_name, error = err);
+        return;
+    }
+
+    entry.reset(clock.now());
+    entry.is_unhealthy.store(false, Ordering::SeqCst);
+
+    host_event!(info, "Stalled service restarted", service_id = service_id, service = type_info.type_name);
+    event_bus.publish(HostEvent::ServiceHealthy { service_id, service_type_info: type_info });
+}
@@ -0,0 +1,439 @@
+use anthill_di::{types::TypeInfo, Constructor};
+use tokio::{
+    sync::{RwLock, mpsc, oneshot},
+    task::{AbortHandle, JoinHandle},
+    time::Instant,
+};
+use std::{
+    any::TypeId,
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Weak},
+    time::Duration,
+};
+
+use crate::{configs::ProcessConfig, events::StopReason, life_time::ILifeTimeManager, types::ServiceManagerResult, time::Clock, metrics::{Metrics, SUPERVISOR_RESTARTS}, trace::{in_service_span, ServicePhase}};
+
+use super::{IBaseService, ServiceFailure, ServiceId, ServiceManager, ProcessService, TaskTracker};
+
+type ServiceFactory = Arc<dyn Fn(Arc<ServiceManager>) -> Pin<Box<dyn Future<Output = ServiceManagerResult<Arc<RwLock<dyn IBaseService>>>> + Send + Sync>> + Send + Sync>;
+
+/// Which children restart when one child fails
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SupervisorStrategy {
+    /// Restart only failed child
+    OneForOne,
+    /// Restart all children
+    OneForAll,
+    /// Restart failed child and all children added after it
+    RestForOne,
+}
+
+/// Supervisor description, register with ```Application::register_supervisor``` or nest with ```add_supervisor```
+#[derive(Clone)]
+pub struct SupervisorBuilder {
+    name: String,
+    strategy: SupervisorStrategy,
+    max_restarts: usize,
+    restart_period: Duration,
+    children: Vec<ServiceFactory>,
+}
+
+impl SupervisorBuilder {
+    /// By default ```SupervisorStrategy::OneForOne``` with 3 restarts per 5s
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            strategy: SupervisorStrategy::OneForOne,
+            max_restarts: 3,
+            restart_period: Duration::from_secs(5),
+            children: Vec::new(),
+        }
+    }
+
+    pub fn get_name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn with_strategy(mut self, strategy: SupervisorStrategy) -> Self {
+        self.strategy = strategy;
+        self
+    }
+
+    /// If more than ```max_restarts``` restarts happen during ```restart_period```, supervisor stops all children and escalates failure
+    pub fn with_intensity(mut self, max_restarts: usize, restart_period: Duration) -> Self {
+        self.max_restarts = max_restarts;
+        self.restart_period = restart_period;
+        self
+    }
+
+    /// Child created from ioc on every (re)start
+    pub fn add_service<TService: IBaseService + Constructor>(mut self) -> Self {
+        self.children.push(Arc::new(|service_manager: Arc<ServiceManager>| Box::pin(async move {
+            service_manager.build_service::<TService>().await
+        })));
+        self
+    }
+
+    /// Process child, use ```ProcessRestartPolicy::Never``` to restart only by supervisor
+    pub fn add_process(mut self, config: ProcessConfig) -> Self {
+        self.children.push(Arc::new(move |service_manager: Arc<ServiceManager>| {
+            let config = config.clone();
+            Box::pin(async move {
+                Ok(Arc::new(RwLock::new(ProcessService::new(config, &service_manager))) as Arc<RwLock<dyn IBaseService>>)
+            })
+        }));
+        self
+    }
+
+    pub fn add_supervisor(mut self, supervisor: SupervisorBuilder) -> Self {
+        self.children.push(Arc::new(move |service_manager: Arc<ServiceManager>| {
+            let supervisor = supervisor.clone();
+            Box::pin(async move {
+                Ok(Arc::new(RwLock::new(Supervisor::new(supervisor, Arc::downgrade(&service_manager), false))) as Arc<RwLock<dyn IBaseService>>)
+            })
+        }));
+        self
+    }
+}
+
+enum SupervisorState {
+    Pending,
+    Started { stop_sender: oneshot::Sender<()>, work_task: JoinHandle<()> },
+}
+
+/// Service which starts its children in order, stops them in reverse order and restarts them on failure
+pub struct Supervisor {
+    builder: SupervisorBuilder,
+    service_manager: Weak<ServiceManager>,
+    state: SupervisorState,
+    failure_receiver: Option<oneshot::Receiver<ServiceFailure>>,
+    abort_handles: Vec<AbortHandle>,
+    is_root: bool,
+}
+
+impl Supervisor {
+    /// Failure of root supervisor (registered in application) stops application, nested supervisor escalates failure to parent
+    pub (crate) fn new(builder: SupervisorBuilder, service_manager: Weak<ServiceManager>, is_root: bool) -> Self {
+        Self {
+            builder,
+            service_manager,
+            state: SupervisorState::Pending,
+            failure_receiver: None,
+            abort_handles: Vec::new(),
+            is_root,
+        }
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for Supervisor {
+    async fn on_start(&mut self) {
+        let service_manager = self.service_manager.upgrade().expect("ServiceManager dropped before supervisor start");
+        let life_time_manager = service_manager.get_context().resolve::<Arc<dyn ILifeTimeManager>>().await
+            .expect("LifeTimeManager not found");
+
+        let (failure_sender, failure_receiver) = oneshot::channel();
+        self.failure_receiver = Some(failure_receiver);
+
+        let type_info = self.get_type_info();
+        let mut runtime = SupervisorRuntime::new(self.builder.clone(), service_manager.clone()).await;
+
+        let start_result = runtime.start_all().await;
+
+        let (stop_sender, stop_receiver) = oneshot::channel();
+        let is_root = self.is_root;
+
+        let work_task = tokio::spawn(async move {
+            let failure = match start_result {
+                Ok(()) => runtime.run(stop_receiver).await,
+                Err(failure) => {
+                    runtime.stop_all().await;
+                    Some(failure)
+                },
+            };
+
+            if let Some(failure) = failure {
+                let failure = ServiceFailure {
+                    message: format!("Supervisor escalated child failure: [{service_type_info:?}] [{message}]", service_type_info = failure.service_type_info, message = failure.message),
+                    service_type_info: type_info,
+                };
+
+                if is_root {
+                    // returned from application run after stop
+                    host_event!(error, "Supervisor failure escalated to life time manager", service = failure.service_type_info.type_name, message = failure.message);
+                    service_manager.set_escalated_failure(failure.clone());
+                    service_manager.set_stop_reason(StopReason::SupervisorEscalated { supervisor_type_info: failure.service_type_info.clone() });
+                    life_time_manager.stop().await;
+                } else {
+                    host_event!(error, "Supervisor failure escalated to parent", service = failure.service_type_info.type_name, message = failure.message);
+                }
+
+                // root supervisor failure reported to service manager
+                _ = failure_sender.send(failure);
+            }
+        });
+
+        // children and their tasks aborted with work task
+        self.abort_handles = vec![work_task.abort_handle()];
+        self.state = SupervisorState::Started { stop_sender, work_task };
+    }
+
+    async fn on_stop(&mut self) {
+        if let SupervisorState::Started { stop_sender, work_task } = std::mem::replace(&mut self.state, SupervisorState::Pending) {
+            _ = stop_sender.send(());
+
+            if let Err(err) = work_task.await {
+                host_event!(error, "Supervisor work task error", supervisor = self.builder.name, error = err);
+            }
+        }
+    }
+
+    fn get_type_info(&self) -> TypeInfo {
+        TypeInfo::new(TypeId::of::<Self>(), format!("{type_name}[{name}]", type_name = std::any::type_name::<Self>(), name = self.builder.name))
+    }
+
+    fn take_failure_receiver(&mut self) -> Option<oneshot::Receiver<ServiceFailure>> {
+        self.failure_receiver.take()
+    }
+
+    fn take_abort_handles(&mut self) -> Vec<AbortHandle> {
+        std::mem::take(&mut self.abort_handles)
+    }
+}
+
+/// In-flight child lifecycle task aborted if supervisor aborted while waiting it
+struct AbortOnDrop(AbortHandle);
+
+impl Drop for AbortOnDrop {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+struct SupervisedChild {
+    service: Arc<RwLock<dyn IBaseService>>,
+    type_info: TypeInfo,
+    /// Child tasks and failure watcher, aborted after child stop
+    abort_handles: Vec<AbortHandle>,
+}
+
+struct SupervisorRuntime {
+    builder: SupervisorBuilder,
+    service_manager: Arc<ServiceManager>,
+    on_start_timeout: Duration,
+    on_stop_timeout: Duration,
+    clock: Arc<Clock>,
+    metrics: Arc<Metrics>,
+    task_tracker: Arc<TaskTracker>,
+    /// Owner of child tasks, kept for all instances of child
+    child_ids: Vec<ServiceId>,
+    children: Vec<Option<SupervisedChild>>,
+    generations: Vec<u64>,
+    restarts: VecDeque<Instant>,
+    failure_sender: mpsc::UnboundedSender<(usize, u64, ServiceFailure)>,
+    failure_receiver: mpsc::UnboundedReceiver<(usize, u64, ServiceFailure)>,
+}
+
+impl SupervisorRuntime {
+    async fn new(builder: SupervisorBuilder, service_manager: Arc<ServiceManager>) -> Self {
+        let (on_start_timeout, on_stop_timeout) = service_manager.get_timeouts().await;
+        let clock = service_manager.get_clock();
+        let metrics = service_manager.get_metrics();
+        let task_tracker = service_manager.get_task_tracker();
+        let (failure_sender, failure_receiver) = mpsc::unbounded_channel();
+        let children_count = builder.children.len();
+        let child_ids = (0..children_count).map(|_| service_manager.create_service_id()).collect();
+
+        Self {
+            builder,
+            service_manager,
+            on_start_timeout,
+            on_stop_timeout,
+            clock,
+            metrics,
+            task_tracker,
+            child_ids,
+            children: (0..children_count).map(|_| None).collect(),
+            generations: vec![0; children_count],
+            restarts: VecDeque::new(),
+            failure_sender,
+            failure_receiver,
+        }
+    }
+
+    /// Return ```None``` if stopped by request, or failure if restart intensity exceeded
+    async fn run(&mut self, mut stop_receiver: oneshot::Receiver<()>) -> Option<ServiceFailure> {
+        loop {
+            let (index, generation, failure) = tokio::select! {
+                _ = &mut stop_receiver => {
+                    self.stop_all().await;
+                    return None;
+                },
+                Some(failure) = self.failure_receiver.recv() => failure,
+            };
+
+            // failure from already restarted child instance
+            if self.generations[index] != generation {
+                continue;
+            }
+
+            host_event!(error, "Supervisor child failed", supervisor = self.builder.name, service = failure.service_type_info.type_name, message = failure.message);
+
+            let now = self.clock.now();
+            self.restarts.push_back(now);
+            while self.restarts.front().map(|restart| now.duration_since(*restart) > self.builder.restart_period).unwrap_or(false) {
+                self.restarts.pop_front();
+            }
+
+            if self.restarts.len() > self.builder.max_restarts {
+                host_event!(error, "Supervisor restart intensity exceeded", supervisor = self.builder.name);
+                self.stop_all().await;
+                return Some(failure);
+            }
+
+            let restart_indexes: Vec<usize> = match self.builder.strategy {
+                SupervisorStrategy::OneForOne => vec![index],
+                SupervisorStrategy::OneForAll => (0..self.children.len()).collect(),
+                SupervisorStrategy::RestForOne => (index..self.children.len()).collect(),
+            };
+
+            for restart_index in restart_indexes.iter().rev() {
+                self.stop_child(*restart_index).await;
+            }
+
+            self.metrics.increment_counter(SUPERVISOR_RESTARTS, &[("supervisor", &self.builder.name), ("service", &failure.service_type_info.type_name)], 1);
+
+            for restart_index in restart_indexes.into_iter() {
+                if let Err(failure) = self.start_child(restart_index).await {
+                    // handle as regular child failure on next iteration
+                    _ = self.failure_sender.send((restart_index, self.generations[restart_index], failure));
+                    break;
+                }
+            }
+        }
+    }
+
+    async fn start_all(&mut self) -> Result<(), ServiceFailure> {
+        for index in 0..self.children.len() {
+            self.start_child(index).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn stop_all(&mut self) {
+        for index in (0..self.children.len()).rev() {
+            self.stop_child(index).await;
+        }
+    }
+
+    async fn start_child(&mut self, index: usize) -> Result<(), ServiceFailure> {
+        self.generations[index] += 1;
+        let generation = self.generations[index];
+        let child_id = self.child_ids[index];
+
+        let service = (self.builder.children[index])(self.service_manager.clone()).await
+            .map_err(|e| ServiceFailure { service_type_info: TypeInfo::from_type::<Supervisor>(), message: e.to_string() })?;
+
+        let type_info = service.read().await.get_type_info();
+        host_event!(info, "Supervisor starting child ...", supervisor = self.builder.name, service = type_info.type_name);
+
+        // every child instance added like service: tasks of previous instance already cancelled on stop
+        self.task_tracker.add_owner(child_id, &type_info);
+        service.write().await.on_added(child_id).await;
+
+        let service_ref = service.clone();
+        let on_start = async move {
+            let mut service_write_guard = service_ref.write().await;
+            service_write_guard.on_start().await;
+            (service_write_guard.take_abort_handles(), service_write_guard.take_failure_receiver())
+        };
+        let start_task = tokio::spawn(in_service_span(on_start, &type_info, ServicePhase::Start));
+        let _start_task_guard = AbortOnDrop(start_task.abort_handle());
+
+        let (mut abort_handles, failure_receiver) = match self.clock.timeout(self.on_start_timeout, start_task).await {
+            Ok(Ok(started_child)) => started_child,
+            Ok(Err(err)) => {
+                self.task_tracker.abort_owner(child_id);
+                return Err(ServiceFailure { service_type_info: type_info, message: err.to_string() });
+            },
+            Err(_) => {
+                self.task_tracker.abort_owner(child_id);
+                return Err(ServiceFailure { service_type_info: type_info, message: format!("Start timeout expired [{timeout:?}]", timeout = self.on_start_timeout) });
+            },
+        };
+
+        if let Some(failure_receiver) = failure_receiver {
+            let failure_sender = self.failure_sender.clone();
+            let failure_watcher = tokio::spawn(async move {
+                if let Ok(failure) = failure_receiver.await {
+                    _ = failure_sender.send((index, generation, failure));
+                }
+            });
+
+            abort_handles.push(failure_watcher.abort_handle());
+        }
+
+        host_event!(info, "Supervisor child started", supervisor = self.builder.name, service = type_info.type_name);
+
+        self.children[index] = Some(SupervisedChild { service, type_info, abort_handles });
+
+        Ok(())
+    }
+
+    async fn stop_child(&mut self, index: usize) {
+        // invalidate failures from stopped instance
+        self.generations[index] += 1;
+
+        let Some(child) = self.children[index].take() else {
+            return;
+        };
+
+        let type_info = child.type_info;
+        host_event!(info, "Supervisor stopping child ...", supervisor = self.builder.name, service = type_info.type_name);
+
+        // tracked tasks aborted on drop if stop timeout expired
+        let tracked_tasks = self.task_tracker.cancel_owner(self.child_ids[index]);
+
+        let service = child.service;
+        let on_stop = async move {
+            service.write().await.on_stop().await;
+
+            if let Some(tracked_tasks) = tracked_tasks {
+                tracked_tasks.wait().await;
+            }
+        };
+        let stop_task = tokio::spawn(in_service_span(on_stop, &type_info, ServicePhase::Stop));
+        let _stop_task_guard = AbortOnDrop(stop_task.abort_handle());
+
+        let stop_result = self.clock.timeout(self.on_stop_timeout, stop_task).await;
+
+        // finished after clean stop, aborted if child not stopped in time
+        for abort_handle in child.abort_handles.into_iter() {
+            abort_handle.abort();
+        }
+
+        match stop_result {
+            Err(_) => host_event!(error, "Supervisor child stop timeout expired", supervisor = self.builder.name, service = type_info.type_name, timeout = self.on_stop_timeout),
+            Ok(Err(err)) => host_event!(error, "Supervisor child stop error", supervisor = self.builder.name, service = type_info.type_name, error = err),
+            Ok(Ok(())) => host_event!(info, "Supervisor child stopped", supervisor = self.builder.name, service = type_info.type_name),
+        }
+    }
+}
+
+impl Drop for SupervisorRuntime {
+    /// Children still running if supervisor aborted, their tasks aborted
+    fn drop(&mut self) {
+        for child in self.children.iter_mut().filter_map(Option::take) {
+            for abort_handle in child.abort_handles.into_iter() {
+                abort_handle.abort();
+            }
+        }
+
+        for child_id in self.child_ids.iter() {
+            self.task_tracker.remove_owner(*child_id);
+        }
+    }
+}
@@ -0,0 +1,199 @@
+use anthill_di::{
+    types::{BuildDependencyResult, TypeInfo},
+    Constructor,
+    DependencyContext,
+};
+use tokio::{
+    sync::watch,
+    task::{AbortHandle, JoinHandle},
+};
+use std::{
+    collections::HashMap,
+    future::Future,
+    sync::{Arc, Mutex},
+};
+
+use crate::types::{TaskSpawnerError, TaskSpawnerResult};
+
+use super::ServiceId;
+
+struct TrackedTask {
+    name: String,
+    task: JoinHandle<()>,
+}
+
+/// Tasks taken from owner on stop, aborted on drop
+pub (crate) struct TrackedTasks {
+    type_info: TypeInfo,
+    tasks: Vec<TrackedTask>,
+}
+
+impl TrackedTasks {
+    /// Wait all tasks, panic of task logged
+    pub (crate) async fn wait(mut self) {
+        for tracked_task in self.tasks.iter_mut() {
+            if let Err(err) = (&mut tracked_task.task).await {
+                host_event!(error, "Tracked task error", service = self.type_info.type_name, task = tracked_task.name, error = err);
+            }
+        }
+
+        self.tasks.clear();
+    }
+}
+
+impl Drop for TrackedTasks {
+    fn drop(&mut self) {
+        for tracked_task in self.tasks.iter().filter(|tracked_task| !tracked_task.task.is_finished()) {
+            host_event!(warn, "Tracked task aborted", service = self.type_info.type_name, task = tracked_task.name);
+            tracked_task.task.abort();
+        }
+    }
+}
+
+/// Tasks and cancellation of one owner run
+struct OwnerTasks {
+    cancel_sender: watch::Sender<bool>,
+    tracked_tasks: TrackedTasks,
+}
+
+impl OwnerTasks {
+    fn new(type_info: TypeInfo) -> Self {
+        Self { cancel_sender: watch::channel(false).0, tracked_tasks: TrackedTasks { type_info, tasks: Vec::new() } }
+    }
+}
+
+/// Child tasks of services grouped by owning service. Resolve ```TaskSpawner``` in service constructor or create it from ```Arc<TaskTracker>```.
+/// On owner stop tasks are cancelled before ```on_stop```, then awaited within stop timeout and aborted if timeout expired or service aborted
+#[derive(Default)]
+pub struct TaskTracker {
+    owners: Mutex<HashMap<ServiceId, OwnerTasks>>,
+}
+
+impl TaskTracker {
+    /// Spawner not bound to service, bind it in ```IBaseService::on_added``` with ```TaskSpawner::bind```
+    pub fn create_spawner(self: &Arc<Self>) -> TaskSpawner {
+        TaskSpawner { tracker: self.clone(), owner: Arc::new(Mutex::new(None)) }
+    }
+
+    /// Names of not finished tasks of service current run
+    pub fn get_running_tasks(&self, service_id: ServiceId) -> Vec<String> {
+        self.with_owner(service_id, |owner_tasks| owner_tasks.tracked_tasks.tasks.iter().map(|tracked_task| tracked_task.name.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Called by service manager when service added
+    pub (crate) fn add_owner(&self, service_id: ServiceId, owner_type_info: &TypeInfo) {
+        self.owners.lock().unwrap().insert(service_id, OwnerTasks::new(owner_type_info.clone()));
+    }
+
+    /// Called by service manager when service removed, not finished tasks aborted
+    pub (crate) fn remove_owner(&self, service_id: ServiceId) {
+        let owner_tasks = self.owners.lock().unwrap().remove(&service_id);
+        drop(owner_tasks);
+    }
+
+    /// New owner run after stop, tasks spawned after cancellation aborted
+    pub (crate) fn start_owner(&self, service_id: ServiceId) {
+        if let Some(owner_tasks) = self.owners.lock().unwrap().get_mut(&service_id) {
+            if *owner_tasks.cancel_sender.borrow() {
+                *owner_tasks = OwnerTasks::new(owner_tasks.tracked_tasks.type_info.clone());
+            }
+        }
+    }
+
+    /// Signal cancellation and take tasks to wait, owner stays cancelled until next start
+    pub (crate) fn cancel_owner(&self, service_id: ServiceId) -> Option<TrackedTasks> {
+        self.with_owner(service_id, |owner_tasks| {
+            owner_tasks.cancel_sender.send_replace(true);
+            TrackedTasks { type_info: owner_tasks.tracked_tasks.type_info.clone(), tasks: std::mem::take(&mut owner_tasks.tracked_tasks.tasks) }
+        })
+    }
+
+    /// Cancel owner and abort all its tasks
+    pub (crate) fn abort_owner(&self, service_id: ServiceId) {
+        let tracked_tasks = self.cancel_owner(service_id);
+        drop(tracked_tasks);
+    }
+
+    fn with_owner<TResult>(&self, service_id: ServiceId, action: impl FnOnce(&mut OwnerTasks) -> TResult) -> Option<TResult> {
+        let mut owners = self.owners.lock().unwrap();
+        let owner_tasks = owners.get_mut(&service_id)?;
+        owner_tasks.tracked_tasks.tasks.retain(|tracked_task| !tracked_task.task.is_finished());
+        Some(action(owner_tasks))
+    }
+}
+
+/// Spawn named child tasks tracked by host instead of raw ```tokio::spawn```. Resolve as ```TaskSpawner``` (new not bound spawner on every resolve).
+/// Clones share owner, so spawner created in constructor can be cloned before bound
+#[derive(Clone)]
+pub struct TaskSpawner {
+    tracker: Arc<TaskTracker>,
+    owner: Arc<Mutex<Option<ServiceId>>>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for TaskSpawner {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        let tracker: Arc<TaskTracker> = ctx.resolve().await?;
+        Ok(tracker.create_spawner())
+    }
+}
+
+impl TaskSpawner {
+    /// Bind spawner to owning service, call from ```IBaseService::on_added```
+    pub fn bind(&self, service_id: ServiceId) {
+        *self.owner.lock().unwrap() = Some(service_id);
+    }
+
+    pub fn get_owner(&self) -> Option<ServiceId> {
+        *self.owner.lock().unwrap()
+    }
+
+    /// Task result is not returned, use channel if needed. Task is not spawned if spawner not bound or owner removed
+    pub fn spawn<TFuture>(&self, name: &str, future: TFuture) -> TaskSpawnerResult<AbortHandle>
+    where
+        TFuture: Future<Output = ()> + Send + 'static
+    {
+        let Some(service_id) = self.get_owner() else {
+            host_event!(error, "Task spawner not bound to service, task not spawned", task = name);
+            return Err(TaskSpawnerError::NotBound { task: name.to_string() });
+        };
+
+        let abort_handle = self.tracker.with_owner(service_id, |owner_tasks| {
+            host_event!(debug, "Tracked task spawned", service = owner_tasks.tracked_tasks.type_info.type_name, task = name);
+
+            let task = tokio::spawn(future);
+            let abort_handle = task.abort_handle();
+            owner_tasks.tracked_tasks.tasks.push(TrackedTask { name: name.to_string(), task });
+
+            abort_handle
+        });
+
+        abort_handle.ok_or_else(|| {
+            host_event!(error, "Task spawner owner not found, task not spawned", service_id = service_id, task = name);
+            TaskSpawnerError::OwnerNotFound { service_id, task: name.to_string() }
+        })
+    }
+
+    /// Completed when owner stop requested (until next owner start), use in task to finish work gracefully.
+    /// Never completed for not bound spawner
+    pub async fn cancelled(&self) {
+        let cancel_receiver = self.get_owner()
+            .and_then(|service_id| self.tracker.with_owner(service_id, |owner_tasks| owner_tasks.cancel_sender.subscribe()));
+
+        let Some(mut cancel_receiver) = cancel_receiver else {
+            return std::future::pending().await;
+        };
+
+        while !*cancel_receiver.borrow() {
+            if cancel_receiver.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+
+    /// Names of not finished tasks of current owner run
+    pub fn get_running_tasks(&self) -> Vec<String> {
+        self.get_owner().map(|service_id| self.tracker.get_running_tasks(service_id)).unwrap_or_default()
+    }
+}
@@ -0,0 +1,213 @@
+use anthill_di::{Constructor, DependencyContext, types::{BuildDependencyError, BuildDependencyResult}};
+use std::{
+    marker::PhantomData,
+    net::TcpListener,
+    os::unix::{
+        io::{AsRawFd, FromRawFd, OwnedFd, RawFd},
+        net::UnixListener,
+    },
+    sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}},
+};
+
+use crate::types::{SocketActivationError, SocketActivationResult};
+
+/// First passed descriptor, see ```sd_listen_fds```
+const LISTEN_FDS_START: RawFd = 3;
+
+/// Name of descriptor if ```LISTEN_FDNAMES``` not set
+const DEFAULT_SOCKET_NAME: &str = "unknown";
+
+struct ActivatedSocket {
+    name: String,
+    fd: OwnedFd,
+}
+
+/// Listening sockets passed by systemd socket activation (```LISTEN_FDS```, ```LISTEN_PID```, ```LISTEN_FDNAMES```).
+/// Registered empty in ```root_ioc_context```, sockets read by ```run_blocking``` or set with ```Application::set_activated_sockets```.
+/// Resolve as ```Arc<ActivatedSockets>``` and take socket by ```FileDescriptorName=```, or register socket by name with ```Application::register_activated_socket```
+#[derive(Default)]
+pub struct ActivatedSockets {
+    sockets: Mutex<Vec<ActivatedSocket>>,
+}
+
+/// Descriptors owned by first ```from_env``` call which took them
+static IS_TAKEN: AtomicBool = AtomicBool::new(false);
+
+impl ActivatedSockets {
+    /// Take ownership of passed descriptors once per process, environment is not changed.
+    /// Call before runtime started, reading environment is not synchronized with ```set_var``` on other threads.
+    /// Empty if variables not set, ```LISTEN_PID``` not set or belongs to other process, or descriptors already taken
+    pub fn from_env() -> Self {
+        let (Ok(listen_pid), Ok(listen_fds)) = (std::env::var("LISTEN_PID"), std::env::var("LISTEN_FDS")) else {
+            return Self::default();
+        };
+
+        if listen_pid.parse::<u32>().ok() != Some(std::process::id()) {
+            host_event!(info, "Socket activation skipped, descriptors passed to other process", listen_pid = listen_pid);
+            return Self::default();
+        }
+
+        let count = match listen_fds.parse::<RawFd>() {
+            Ok(count) if count > 0 => count,
+            _ => {
+                host_event!(error, "Socket activation skipped, invalid LISTEN_FDS", listen_fds = listen_fds);
+                return Self::default();
+            },
+        };
+
+        if IS_TAKEN.swap(true, Ordering::SeqCst) {
+            host_event!(warn, "Socket activation skipped, descriptors already taken");
+            return Self::default();
+        }
+
+        let listen_fdnames = std::env::var("LISTEN_FDNAMES").ok();
+        let mut names = listen_fdnames.as_deref().unwrap_or_default().split(':');
+
+        let sockets = (LISTEN_FDS_START..LISTEN_FDS_START + count)
+            .filter_map(|fd| {
+                let name = names.next().filter(|name| !name.is_empty()).unwrap_or(DEFAULT_SOCKET_NAME).to_string();
+
+                // SAFETY: only descriptor flags changed, fails if descriptor is not open
+                if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } < 0 {
+                    host_event!(error, "Activated socket not passed", name = name, fd = fd, error = std::io::Error::last_os_error());
+                    return None;
+                }
+
+                // SAFETY: descriptor is open, passed by protocol to this process and not used elsewhere
+                let fd = unsafe { OwnedFd::from_raw_fd(fd) };
+
+                host_event!(info, "Activated socket received", name = name, fd = fd.as_raw_fd());
+
+                Some(ActivatedSocket { name, fd })
+            })
+            .collect();
+
+        Self { sockets: Mutex::new(sockets) }
+    }
+
+    /// Move sockets of other instance to this one
+    pub fn append(&self, other: ActivatedSockets) {
+        let other_sockets = other.sockets.into_inner().unwrap();
+        self.sockets.lock().unwrap().extend(other_sockets);
+    }
+
+    /// Names of sockets not taken yet, same name may repeat
+    pub fn get_names(&self) -> Vec<String> {
+        self.sockets.lock().unwrap().iter().map(|socket| socket.name.clone()).collect()
+    }
+
+    /// Take first socket with name as raw descriptor (UDP, netlink, ...)
+    pub fn take_fd(&self, name: &str) -> SocketActivationResult<OwnedFd> {
+        self.take(name, |_| true, "any")
+    }
+
+    /// Take first TCP socket with name, call ```set_nonblocking(true)``` before ```tokio::net::TcpListener::from_std```
+    pub fn take_tcp_listener(&self, name: &str) -> SocketActivationResult<TcpListener> {
+        self.take(name, |fd| is_listener(fd, &[libc::AF_INET, libc::AF_INET6]), "tcp").map(TcpListener::from)
+    }
+
+    /// Take first unix socket with name, call ```set_nonblocking(true)``` before ```tokio::net::UnixListener::from_std```
+    pub fn take_unix_listener(&self, name: &str) -> SocketActivationResult<UnixListener> {
+        self.take(name, |fd| is_listener(fd, &[libc::AF_UNIX]), "unix").map(UnixListener::from)
+    }
+
+    /// Socket with unexpected type left in place
+    fn take(&self, name: &str, type_match: impl Fn(&OwnedFd) -> bool, expected: &str) -> SocketActivationResult<OwnedFd> {
+        let mut sockets = self.sockets.lock().unwrap();
+
+        let position = sockets.iter().position(|socket| socket.name == name)
+            .ok_or_else(|| SocketActivationError::SocketNotFound { name: name.to_string() })?;
+
+        if !type_match(&sockets[position].fd) {
+            return Err(SocketActivationError::UnexpectedSocketType { name: name.to_string(), expected: expected.to_string() });
+        }
+
+        Ok(sockets.remove(position).fd)
+    }
+}
+
+/// Activated socket name (```FileDescriptorName=```) as type, register with ```Application::register_activated_socket```
+pub trait IActivatedSocketName: Sync + Send + 'static {
+    const NAME: &'static str;
+}
+
+/// TCP listener with name ```TName::NAME```, taken from ```ActivatedSockets``` on resolve, so resolved once
+pub struct ActivatedTcpListener<TName: IActivatedSocketName> {
+    listener: TcpListener,
+    name: PhantomData<TName>,
+}
+
+impl<TName: IActivatedSocketName> ActivatedTcpListener<TName> {
+    pub fn into_inner(self) -> TcpListener {
+        self.listener
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TName: IActivatedSocketName> Constructor for ActivatedTcpListener<TName> {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        let listener = ctx.resolve::<Arc<ActivatedSockets>>().await?.take_tcp_listener(TName::NAME)
+            .map_err(|err| BuildDependencyError::Custom { message: err.to_string() })?;
+
+        Ok(Self { listener, name: PhantomData })
+    }
+}
+
+/// Unix listener with name ```TName::NAME```, taken from ```ActivatedSockets``` on resolve, so resolved once
+pub struct ActivatedUnixListener<TName: IActivatedSocketName> {
+    listener: UnixListener,
+    name: PhantomData<TName>,
+}
+
+impl<TName: IActivatedSocketName> ActivatedUnixListener<TName> {
+    pub fn into_inner(self) -> UnixListener {
+        self.listener
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl<TName: IActivatedSocketName> Constructor for ActivatedUnixListener<TName> {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        let listener = ctx.resolve::<Arc<ActivatedSockets>>().await?.take_unix_listener(TName::NAME)
+            .map_err(|err| BuildDependencyError::Custom { message: err.to_string() })?;
+
+        Ok(Self { listener, name: PhantomData })
+    }
+}
+
+fn is_listener(fd: &OwnedFd, families: &[libc::c_int]) -> bool {
+    is_stream_socket(fd) && is_accepting(fd) && socket_family(fd).is_some_and(|family| families.contains(&family))
+}
+
+fn socket_family(fd: &OwnedFd) -> Option<libc::c_int> {
+    // SAFETY: storage is large enough for any address, length passed accordingly
+    unsafe {
+        let mut storage: libc::sockaddr_storage = std::mem::zeroed();
+        let mut len = std::mem::size_of::<libc::sockaddr_storage>() as libc::socklen_t;
+
+        (libc::getsockname(fd.as_raw_fd(), &mut storage as *mut _ as *mut libc::sockaddr, &mut len) == 0)
+            .then_some(storage.ss_family as libc::c_int)
+    }
+}
+
+fn is_stream_socket(fd: &OwnedFd) -> bool {
+    // SAFETY: option value is c_int, length passed accordingly
+    unsafe {
+        let mut socket_type: libc::c_int = 0;
+        let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+
+        libc::getsockopt(fd.as_raw_fd(), libc::SOL_SOCKET, libc::SO_TYPE, &mut socket_type as *mut _ as *mut libc::c_void, &mut len) == 0
+            && socket_type == libc::SOCK_STREAM
+    }
+}
+
+fn is_accepting(fd: &OwnedFd) -> bool {
+    // SAFETY: option value is c_int, length passed accordingly
+    unsafe {
+        let mut is_accepting: libc::c_int = 0;
+        let mut len = std::mem::size_of::<libc::c_int>() as libc::socklen_t;
+
+        libc::getsockopt(fd.as_raw_fd(), libc::SOL_SOCKET, libc::SO_ACCEPTCONN, &mut is_accepting as *mut _ as *mut libc::c_void, &mut len) == 0
+            && is_accepting != 0
+    }
+}
@@ -1,17 +1,27 @@
 use std::sync::Arc;
 
 use anthill_di::{DependencyContext, types::TypeInfo};
-use anthill_di_configuration_extension::{ConfigurationSnapshot, source::JsonFileConfiguration};
 use tokio::sync::RwLock;
-use crate::{configs::CoreConfig, Application};
+use crate::{configs::CoreConfigSnapshot, Application, types::StartupResult};
 
 
+/// All startups ```configure_dependency``` called first, then all startups ```configure_application```.
+/// Startups ordered by dependencies, then by priority (lower first), then by registration order
 #[async_trait_with_sync::async_trait(Sync)]
 pub trait IStartup: Sync + Send + 'static {
-    async fn configure_application(&mut self, core_config: Arc<RwLock<ConfigurationSnapshot<CoreConfig, JsonFileConfiguration::<CoreConfig>>>>, app: &mut Application);
-    async fn configure_dependency(&mut self, root_ioc_context: &mut DependencyContext);
+    async fn configure_application(&mut self, core_config: Arc<RwLock<CoreConfigSnapshot>>, app: &mut Application) -> StartupResult;
+    async fn configure_dependency(&mut self, root_ioc_context: &mut DependencyContext) -> StartupResult;
 
     fn get_type_info(&self) -> TypeInfo {
         TypeInfo::from_type::<Self>()
-    }    
-}
\ No newline at end of file
+    }
+
+    fn get_priority(&self) -> i32 {
+        0
+    }
+
+    /// Startups which must be applied before this one, ```TypeInfo::from_type::<TStartup>()```
+    fn get_dependencies(&self) -> Vec<TypeInfo> {
+        Vec::new()
+    }
+}
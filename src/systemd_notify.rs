@@ -0,0 +1,186 @@
+use std::{
+    io,
+    os::unix::net::UnixDatagram,
+    sync::Arc,
+    time::Duration,
+};
+
+use tokio::{sync::broadcast::error::RecvError, task::JoinHandle};
+
+use crate::{
+    events::{HostEvent, HostEventBus},
+    time::Clock,
+};
+
+/// systemd notify protocol client (```Type=notify``` units), sends datagrams to ```NOTIFY_SOCKET```.
+/// Application sends ```READY=1``` after start, ```STOPPING=1``` when shutdown begins, ```STATUS=``` with service progress and ```WATCHDOG=1``` pings
+pub struct SystemdNotifier {
+    socket: UnixDatagram,
+    watchdog_interval: Option<Duration>,
+}
+
+impl SystemdNotifier {
+    /// Connect to socket path, path started with ```@``` is abstract socket (linux only)
+    pub fn connect(path: &str) -> io::Result<Self> {
+        let socket = UnixDatagram::unbound()?;
+        // called from runtime threads, full receiver queue must not block them
+        socket.set_nonblocking(true)?;
+
+        match path.strip_prefix('@') {
+            Some(name) => connect_abstract(&socket, name)?,
+            None => socket.connect(path)?,
+        }
+
+        Ok(Self { socket, watchdog_interval: None })
+    }
+
+    /// Notifier from ```NOTIFY_SOCKET```, watchdog ping interval is half of ```WATCHDOG_USEC``` if ```WATCHDOG_PID``` not set or match current process.
+    /// None if not running under systemd or socket unavailable
+    pub fn from_env() -> Option<Self> {
+        let path = std::env::var("NOTIFY_SOCKET").ok().filter(|path| !path.is_empty())?;
+
+        let notifier = match Self::connect(&path) {
+            Ok(notifier) => notifier,
+            Err(err) => {
+                host_event!(error, "Systemd notify socket connect error", socket = path, error = err);
+                return None;
+            },
+        };
+
+        let watchdog_pid_match = std::env::var("WATCHDOG_PID").ok()
+            .and_then(|pid| pid.parse::<u32>().ok())
+            .is_none_or(|pid| pid == std::process::id());
+
+        let watchdog_interval = std::env::var("WATCHDOG_USEC").ok()
+            .and_then(|usec| usec.parse::<u64>().ok())
+            .filter(|usec| *usec > 0 && watchdog_pid_match)
+            .map(|usec| Duration::from_micros(usec / 2));
+
+        Some(match watchdog_interval {
+            Some(watchdog_interval) => notifier.with_watchdog_interval(watchdog_interval),
+            None => notifier,
+        })
+    }
+
+    pub fn with_watchdog_interval(mut self, watchdog_interval: Duration) -> Self {
+        self.watchdog_interval = Some(watchdog_interval);
+        self
+    }
+
+    pub fn get_watchdog_interval(&self) -> Option<Duration> {
+        self.watchdog_interval
+    }
+
+    /// Send raw state, multiple assignments separated by ```\n```
+    pub fn notify(&self, state: &str) -> io::Result<()> {
+        self.socket.send(state.as_bytes()).map(|_| ())
+    }
+
+    pub fn ready(&self) -> io::Result<()> {
+        self.notify("READY=1")
+    }
+
+    pub fn stopping(&self) -> io::Result<()> {
+        self.notify("STOPPING=1")
+    }
+
+    /// Status line, new lines replaced by space
+    pub fn status(&self, status: &str) -> io::Result<()> {
+        self.notify(&format!("STATUS={}", status.replace('\n', " ")))
+    }
+
+    pub fn watchdog(&self) -> io::Result<()> {
+        self.notify("WATCHDOG=1")
+    }
+
+    /// Send failure logged, systemd not available must not break application
+    pub(crate) fn send(&self, state: &str) {
+        if let Err(err) = self.notify(state) {
+            host_event!(warn, "Systemd notify error", state = state, error = err);
+        }
+    }
+}
+
+#[cfg(any(target_os = "linux", target_os = "android"))]
+fn connect_abstract(socket: &UnixDatagram, name: &str) -> io::Result<()> {
+    #[cfg(target_os = "android")]
+    use std::os::android::net::SocketAddrExt;
+    #[cfg(target_os = "linux")]
+    use std::os::linux::net::SocketAddrExt;
+
+    socket.connect_addr(&std::os::unix::net::SocketAddr::from_abstract_name(name)?)
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "android")))]
+fn connect_abstract(_socket: &UnixDatagram, name: &str) -> io::Result<()> {
+    Err(io::Error::new(io::ErrorKind::Unsupported, format!("abstract socket not supported [@{name}]")))
+}
+
+/// Background tasks of notifier: lifecycle notifications and service progress status from host events, watchdog pings.
+/// Single event task keeps notifications in publish order
+pub(crate) struct SystemdNotifyTasks {
+    event_task: Option<JoinHandle<()>>,
+    watchdog_task: Option<JoinHandle<()>>,
+}
+
+impl SystemdNotifyTasks {
+    /// Subscribe before services start, otherwise progress events are lost
+    pub(crate) fn spawn(notifier: Arc<SystemdNotifier>, event_bus: &HostEventBus, clock: Arc<Clock>) -> Self {
+        let mut events = event_bus.subscribe();
+        let event_notifier = notifier.clone();
+
+        let event_task = tokio::spawn(async move {
+            loop {
+                let event = match events.recv().await {
+                    Ok(event) => event,
+                    Err(RecvError::Lagged(..)) => continue,
+                    Err(RecvError::Closed) => break,
+                };
+
+                let state = match event {
+                    HostEvent::ServiceStarting { service_type_info, .. } => format!("STATUS=Starting [{}]", service_type_info.type_name),
+                    HostEvent::ServiceStarted { service_type_info, .. } => format!("STATUS=Started [{}]", service_type_info.type_name),
+                    HostEvent::ServiceFailed { service_type_info, .. } => format!("STATUS=Failed [{}]", service_type_info.type_name),
+                    HostEvent::ServiceStopping { service_type_info, .. } => format!("STATUS=Stopping [{}]", service_type_info.type_name),
+                    HostEvent::ServiceStopped { service_type_info, .. } => format!("STATUS=Stopped [{}]", service_type_info.type_name),
+                    HostEvent::ServiceUnhealthy { service_type_info, .. } => format!("STATUS=Unhealthy [{}]", service_type_info.type_name),
+                    HostEvent::ApplicationStarted => "READY=1\nSTATUS=Running".to_string(),
+                    HostEvent::StopRequested { .. } => "STOPPING=1\nSTATUS=Stopping".to_string(),
+                    HostEvent::ApplicationStopped => {
+                        event_notifier.send("STATUS=Stopped");
+                        break;
+                    },
+                    _ => continue,
+                };
+
+                event_notifier.send(&state);
+            }
+        });
+
+        let watchdog_task = notifier.get_watchdog_interval().map(|watchdog_interval| tokio::spawn(async move {
+            loop {
+                notifier.send("WATCHDOG=1");
+                clock.sleep(watchdog_interval).await;
+            }
+        }));
+
+        Self { event_task: Some(event_task), watchdog_task }
+    }
+
+    /// Stop watchdog pings and wait for notifications until ```HostEvent::ApplicationStopped```
+    pub(crate) async fn finish(mut self) {
+        if let Some(watchdog_task) = self.watchdog_task.take() {
+            watchdog_task.abort();
+        }
+
+        if let Some(event_task) = self.event_task.take() {
+            _ = event_task.await;
+        }
+    }
+}
+
+impl Drop for SystemdNotifyTasks {
+    fn drop(&mut self) {
+        self.event_task.iter().chain(self.watchdog_task.iter()).for_each(JoinHandle::abort);
+    }
+}
@@ -0,0 +1,5 @@
+mod test_life_time_manager;
+pub use test_life_time_manager::*;
+
+mod test_host;
+pub use test_host::*;
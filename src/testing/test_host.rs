@@ -0,0 +1,226 @@
+use anthill_di::types::{BuildDependencyResult, BuildDependencyError, TypeInfo};
+use tokio::{sync::broadcast::{self, error::{RecvError, TryRecvError}}, task::JoinHandle, time::timeout};
+use std::{sync::{Arc, Mutex}, time::Duration};
+
+use crate::{
+    Application,
+    configs::{CoreConfig, ConfigurationSource, InMemoryConfiguration},
+    events::{HostEvent, HostEventBus},
+    life_time::{ILifeTimeManager, ShutdownSignal},
+    services::{ServiceId, ServiceManager, ServiceState, ServiceStateChange},
+    types::AppRunResult,
+};
+
+use super::TestLifeTimeManager;
+
+/// Error collected by ```TestHost```
+#[derive(Debug, Clone, PartialEq)]
+pub enum TestHostError {
+    /// Service switched to ```ServiceState::Failed``` (start error, failure after start, stop timeout)
+    ServiceFailed { service_id: ServiceId, service_type_info: TypeInfo },
+    /// Application run finished with error
+    AppRunFailed { message: String },
+}
+
+/// Receivers drained by background task and before every read, so read sees everything published before it
+struct Collector {
+    state_receiver: broadcast::Receiver<ServiceStateChange>,
+    event_receiver: broadcast::Receiver<HostEvent>,
+    state_changes: Vec<ServiceStateChange>,
+    host_events: Vec<HostEvent>,
+    errors: Vec<TestHostError>,
+}
+
+impl Collector {
+    fn collect(&mut self) {
+        while let Some(state_change) = try_recv(&mut self.state_receiver) {
+            self.state_changes.push(state_change);
+        }
+
+        while let Some(host_event) = try_recv(&mut self.event_receiver) {
+            if let HostEvent::ServiceFailed { service_id, service_type_info } = &host_event {
+                self.errors.push(TestHostError::ServiceFailed { service_id: *service_id, service_type_info: service_type_info.clone() });
+            }
+
+            self.host_events.push(host_event);
+        }
+    }
+}
+
+/// Application host for integration tests: in-memory ```CoreConfig```, ```TestLifeTimeManager```,
+/// application run in background, service state changes, host events and errors collected
+pub struct TestHost {
+    app: Option<Application>,
+    run_task: Option<JoinHandle<(Application, AppRunResult)>>,
+    core_config_source: InMemoryConfiguration<CoreConfig>,
+    life_time_manager: Arc<TestLifeTimeManager>,
+    service_manager: Arc<ServiceManager>,
+    shutdown_signal: Arc<ShutdownSignal>,
+    collector: Arc<Mutex<Collector>>,
+}
+
+impl TestHost {
+    pub async fn new() -> BuildDependencyResult<Self> {
+        Self::with_core_config(CoreConfig::default()).await
+    }
+
+    pub async fn with_core_config(core_config: CoreConfig) -> BuildDependencyResult<Self> {
+        let core_config_source = InMemoryConfiguration::new(&core_config);
+        let mut app = Application::with_configuration_source(ConfigurationSource::InMemory(core_config_source.clone())).await?;
+
+        app.register_life_time_manager_instance(TestLifeTimeManager::default()).await
+            .map_err(|e| BuildDependencyError::Custom { message: e.to_string() })?;
+
+        let life_time_manager = app.root_ioc_context.resolve().await?;
+        let shutdown_signal = app.root_ioc_context.resolve().await?;
+        let service_manager = app.service_manager.clone();
+
+        let event_bus = app.root_ioc_context.resolve::<Arc<HostEventBus>>().await?;
+
+        let collector = Arc::new(Mutex::new(Collector {
+            state_receiver: service_manager.subscribe_state_changes(),
+            event_receiver: event_bus.subscribe(),
+            state_changes: Vec::new(),
+            host_events: Vec::new(),
+            errors: Vec::new(),
+        }));
+
+        // every state change and host event wakes collector, channels don't lag during long runs
+        spawn_collector_wake(service_manager.subscribe_state_changes(), collector.clone());
+        spawn_collector_wake(event_bus.subscribe(), collector.clone());
+
+        Ok(Self {
+            app: Some(app),
+            run_task: None,
+            core_config_source,
+            life_time_manager,
+            service_manager,
+            shutdown_signal,
+            collector,
+        })
+    }
+
+    /// Application for registrations, available before start and after stop
+    pub fn get_app(&mut self) -> &mut Application {
+        self.app.as_mut().expect("Application running")
+    }
+
+    pub fn get_life_time_manager(&self) -> Arc<TestLifeTimeManager> {
+        self.life_time_manager.clone()
+    }
+
+    pub fn get_service_manager(&self) -> Arc<ServiceManager> {
+        self.service_manager.clone()
+    }
+
+    pub fn get_shutdown_signal(&self) -> Arc<ShutdownSignal> {
+        self.shutdown_signal.clone()
+    }
+
+    /// ```CoreConfig``` stored by application on stop
+    pub fn get_stored_core_config(&self) -> CoreConfig {
+        self.core_config_source.get_value().expect("Stored CoreConfig is invalid")
+    }
+
+    /// Run application in background
+    pub fn start(&mut self) {
+        let mut app = self.app.take().expect("Application already running");
+
+        self.run_task = Some(tokio::spawn(async move {
+            let result = app.run().await;
+            (app, result)
+        }));
+    }
+
+    /// Request stop and wait for application run result
+    pub async fn stop(&mut self) -> AppRunResult {
+        self.life_time_manager.stop().await;
+        self.wait_for_exit().await
+    }
+
+    /// Request abort and wait for application run result
+    pub async fn abort(&mut self) -> AppRunResult {
+        self.life_time_manager.abort();
+        self.wait_for_exit().await
+    }
+
+    /// Wait for application run result without stop request, run error also collected
+    pub async fn wait_for_exit(&mut self) -> AppRunResult {
+        let run_task = self.run_task.take().expect("Application not running");
+        let (app, result) = run_task.await.expect("Application run panicked");
+        self.app = Some(app);
+
+        if let Err(err) = &result {
+            let mut collector = self.collector.lock().unwrap();
+            collector.collect();
+            collector.errors.push(TestHostError::AppRunFailed { message: err.to_string() });
+        }
+
+        result
+    }
+
+    /// Wait until any service of type reached state since host created (transient states included), return ```false``` if timeout expired
+    pub async fn wait_for_state<TService: 'static>(&self, state: ServiceState, timeout_duration: Duration) -> bool {
+        // subscribed before check, change published after check wakes receiver
+        let mut state_receiver = self.service_manager.subscribe_state_changes();
+
+        timeout(timeout_duration, async {
+            while !self.get_service_states::<TService>().contains(&state) {
+                if let Err(RecvError::Closed) = state_receiver.recv().await {
+                    std::future::pending::<()>().await;
+                }
+            }
+        }).await.is_ok()
+    }
+
+    /// All collected service state changes
+    pub fn get_events(&self) -> Vec<ServiceStateChange> {
+        self.with_collector(|collector| collector.state_changes.clone())
+    }
+
+    /// Collected state changes of service type
+    pub fn get_service_states<TService: 'static>(&self) -> Vec<ServiceState> {
+        let type_info = TypeInfo::from_type::<TService>();
+
+        self.with_collector(|collector| collector.state_changes.iter()
+            .filter(|event| event.service_type_info == type_info)
+            .map(|event| event.state)
+            .collect())
+    }
+
+    /// All collected host events
+    pub fn get_host_events(&self) -> Vec<HostEvent> {
+        self.with_collector(|collector| collector.host_events.clone())
+    }
+
+    /// Service failures and application run error in order of occurrence
+    pub fn get_errors(&self) -> Vec<TestHostError> {
+        self.with_collector(|collector| collector.errors.clone())
+    }
+
+    fn with_collector<TResult>(&self, action: impl FnOnce(&Collector) -> TResult) -> TResult {
+        let mut collector = self.collector.lock().unwrap();
+        collector.collect();
+        action(&collector)
+    }
+}
+
+/// Finished when sender (service manager or event bus) dropped
+fn spawn_collector_wake<TEvent: Clone + Send + 'static>(mut wake_receiver: broadcast::Receiver<TEvent>, collector: Arc<Mutex<Collector>>) {
+    tokio::spawn(async move {
+        while !matches!(wake_receiver.recv().await, Err(RecvError::Closed)) {
+            collector.lock().unwrap().collect();
+        }
+    });
+}
+
+/// ```None``` if channel empty or closed, skipped events reported
+fn try_recv<TEvent: Clone>(receiver: &mut broadcast::Receiver<TEvent>) -> Option<TEvent> {
+    loop {
+        match receiver.try_recv() {
+            Ok(event) => return Some(event),
+            Err(TryRecvError::Lagged(count)) => host_event!(warn, "Test host skipped events", count = count),
+            Err(TryRecvError::Empty | TryRecvError::Closed) => return None,
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use tokio::sync::watch;
+
+use crate::life_time::ILifeTimeManager;
+
+/// Life time manager controlled by test, resolve as ```Arc<TestLifeTimeManager>```
+pub struct TestLifeTimeManager {
+    is_running_sender: watch::Sender<bool>,
+    is_running_receiver: watch::Receiver<bool>,
+    is_aborted_sender: watch::Sender<bool>,
+    is_aborted_receiver: watch::Receiver<bool>,
+}
+
+impl Default for TestLifeTimeManager {
+    fn default() -> Self {
+        let (is_running_sender, is_running_receiver) = watch::channel(true);
+        let (is_aborted_sender, is_aborted_receiver) = watch::channel(false);
+
+        Self { is_running_sender, is_running_receiver, is_aborted_sender, is_aborted_receiver }
+    }
+}
+
+impl TestLifeTimeManager {
+    /// Request stop and skip graceful shutdown phases
+    pub fn abort(&self) {
+        _ = self.is_running_sender.send(false);
+        _ = self.is_aborted_sender.send(true);
+    }
+}
+
+async fn wait_for_value(mut receiver: watch::Receiver<bool>, value: bool) {
+    while *receiver.borrow() != value {
+        if receiver.changed().await.is_err() {
+            return;
+        }
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl ILifeTimeManager for TestLifeTimeManager {
+    async fn stop(&self) {
+        _ = self.is_running_sender.send(false);
+    }
+
+    async fn is_running(&self) -> bool {
+        *self.is_running_receiver.borrow()
+    }
+
+    async fn wait_for_stop(&self) {
+        wait_for_value(self.is_running_receiver.clone(), false).await
+    }
+
+    async fn wait_for_abort(&self) {
+        wait_for_value(self.is_aborted_receiver.clone(), true).await
+    }
+}
@@ -0,0 +1,187 @@
+use std::sync::{Arc, Mutex};
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::{
+    services::IBaseService,
+    hooks::{IPreStartHook, IPostStopHook},
+    life_time::ILifeTimeManager,
+    types::{HookResult, HookError},
+};
+
+use anthill_di_derive::constructor;
+
+#[derive(Default)]
+struct TestHookLog {
+    records: Mutex<Vec<String>>,
+}
+
+impl TestHookLog {
+    fn push(&self, record: &str) {
+        self.records.lock().unwrap().push(record.to_string());
+    }
+}
+
+#[derive(constructor)]
+struct TestMigrationHook {
+    log: Arc<TestHookLog>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IPreStartHook for TestMigrationHook {
+    async fn on_pre_start(&mut self) -> HookResult {
+        self.log.push("pre_start");
+        Ok(())
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IPostStopHook for TestMigrationHook {
+    async fn on_post_stop(&mut self) -> HookResult {
+        self.log.push("post_stop");
+        Ok(())
+    }
+}
+
+#[derive(constructor)]
+struct TestFailingHook {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IPreStartHook for TestFailingHook {
+    async fn on_pre_start(&mut self) -> HookResult {
+        Err(HookError::Custom { message: "migration failed".to_string() })
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IPostStopHook for TestFailingHook {
+    async fn on_post_stop(&mut self) -> HookResult {
+        Err(HookError::Custom { message: "cleanup failed".to_string() })
+    }
+}
+
+#[derive(constructor)]
+struct TestHostedService {
+    log: Arc<TestHookLog>,
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestHostedService {
+    async fn on_start(&mut self) {
+        self.log.push("on_start");
+        self.application_life_time.stop().await;
+    }
+
+    async fn on_stop(&mut self) {
+        self.log.push("on_stop");
+    }
+}
+
+#[tokio::test]
+async fn application_hooks() {
+    use crate::{Application, life_time::InnerStateLifeTimeManager};
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.root_ioc_context.register_instance(TestHookLog::default()).await.unwrap();
+
+    app.register_pre_start_hook::<TestMigrationHook>().await.unwrap();
+    app.register_post_stop_hook::<TestMigrationHook>().await.unwrap();
+    app.register_service::<TestHostedService>().await.unwrap();
+
+    app.run().await.unwrap();
+
+    let log = app.root_ioc_context.resolve::<Arc<TestHookLog>>().await.unwrap();
+    assert_eq!(vec!["pre_start", "on_start", "on_stop", "post_stop"], *log.records.lock().unwrap());
+}
+
+#[tokio::test]
+async fn application_pre_start_hook_failed() {
+    use crate::{Application, life_time::InnerStateLifeTimeManager, types::AppRunError};
+    use anthill_di::types::TypeInfo;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.root_ioc_context.register_instance(TestHookLog::default()).await.unwrap();
+
+    app.register_pre_start_hook::<TestFailingHook>().await.unwrap();
+    app.register_service::<TestHostedService>().await.unwrap();
+
+    let result = app.run().await;
+
+    assert_eq!(result.err(), Some(AppRunError::PreStartHookFailed {
+        hook_type_info: TypeInfo::from_type::<TestFailingHook>(),
+        err: HookError::Custom { message: "migration failed".to_string() },
+    }));
+
+    let log = app.root_ioc_context.resolve::<Arc<TestHookLog>>().await.unwrap();
+    assert!(log.records.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn application_pre_start_hook_failed_cleanup() {
+    use crate::{Application, events::{HostEvent, HostEventBus}, life_time::InnerStateLifeTimeManager, types::AppRunError};
+    use anthill_di::types::TypeInfo;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.root_ioc_context.register_instance(TestHookLog::default()).await.unwrap();
+
+    app.register_pre_start_hook::<TestFailingHook>().await.unwrap();
+    app.register_post_stop_hook::<TestMigrationHook>().await.unwrap();
+
+    let mut events = app.root_ioc_context.resolve::<Arc<HostEventBus>>().await.unwrap().subscribe();
+
+    let result = app.run().await;
+
+    assert_eq!(result.err(), Some(AppRunError::PreStartHookFailed {
+        hook_type_info: TypeInfo::from_type::<TestFailingHook>(),
+        err: HookError::Custom { message: "migration failed".to_string() },
+    }));
+
+    // failed start stopped like regular one
+    let log = app.root_ioc_context.resolve::<Arc<TestHookLog>>().await.unwrap();
+    assert_eq!(vec!["post_stop"], *log.records.lock().unwrap());
+
+    let mut received = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        received.push(event);
+    }
+
+    assert!(!received.contains(&HostEvent::ApplicationStarted));
+    assert_eq!(Some(&HostEvent::ApplicationStopped), received.last());
+}
+
+#[tokio::test]
+async fn application_post_stop_hook_failed() {
+    use crate::{Application, events::{HostEvent, HostEventBus}, life_time::InnerStateLifeTimeManager, types::AppRunError};
+    use anthill_di::types::TypeInfo;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.root_ioc_context.register_instance(TestHookLog::default()).await.unwrap();
+
+    app.register_post_stop_hook::<TestFailingHook>().await.unwrap();
+    app.register_service::<TestHostedService>().await.unwrap();
+
+    let mut events = app.root_ioc_context.resolve::<Arc<HostEventBus>>().await.unwrap().subscribe();
+
+    let result = app.run().await;
+
+    assert_eq!(result.err(), Some(AppRunError::PostStopHookFailed {
+        hook_type_info: TypeInfo::from_type::<TestFailingHook>(),
+        err: HookError::Custom { message: "cleanup failed".to_string() },
+    }));
+
+    // stopped event published before error returned
+    let mut received = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        received.push(event);
+    }
+
+    assert_eq!(Some(&HostEvent::ApplicationStopped), received.last());
+}
@@ -0,0 +1,86 @@
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+
+use anthill_di::DependencyContext;
+use tokio::sync::RwLock;
+
+use crate::{
+    configs::CoreConfigSnapshot,
+    life_time::ILifeTimeManager,
+    services::IBackgroundService,
+    types::StartupResult,
+    Application,
+    IStartup,
+};
+
+use anthill_di_derive::constructor;
+
+static EXECUTED: AtomicBool = AtomicBool::new(false);
+static CONFIGURED: AtomicBool = AtomicBool::new(false);
+
+#[crate::background_service]
+#[derive(constructor)]
+struct TestAutoService {
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestAutoService {
+    async fn execute(&self) {
+        EXECUTED.store(true, Ordering::SeqCst);
+        self.application_life_time.stop().await;
+    }
+}
+
+#[crate::startup]
+#[derive(constructor)]
+struct TestAutoStartup {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IStartup for TestAutoStartup {
+    async fn configure_dependency(&mut self, _root_ioc_context: &mut DependencyContext) -> StartupResult {
+        Ok(())
+    }
+
+    async fn configure_application(&mut self, _core_config: Arc<RwLock<CoreConfigSnapshot>>, app: &mut Application) -> StartupResult {
+        app.register_life_time_manager::<crate::life_time::InnerStateLifeTimeManager>().await.unwrap();
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn auto_registration() {
+    use crate::{configs::{CoreConfig, ConfigurationSource}, registration::AutoRegistration};
+
+    let mut type_names: Vec<_> = AutoRegistration::iter().map(|registration| (registration.type_name)()).collect();
+    type_names.sort();
+    assert_eq!(vec![std::any::type_name::<TestAutoService>(), std::any::type_name::<TestAutoStartup>()], type_names);
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    app.register_auto_services().await.unwrap();
+
+    app.run().await.unwrap();
+
+    assert!(EXECUTED.load(Ordering::SeqCst));
+}
+
+mod entry {
+    use std::sync::atomic::Ordering;
+
+    use crate::Application;
+
+    #[crate::main(config = "auto_registration_main.json")]
+    pub (crate) async fn main(app: &mut Application) {
+        assert!(app.service_manager.get_service_ids().await.is_empty());
+        super::CONFIGURED.store(true, Ordering::SeqCst);
+    }
+}
+
+#[test]
+fn auto_registration_main() {
+    entry::main().unwrap();
+
+    assert!(CONFIGURED.load(Ordering::SeqCst));
+    assert!(EXECUTED.load(Ordering::SeqCst));
+
+    std::fs::remove_file("auto_registration_main.json").unwrap();
+}
@@ -13,6 +13,7 @@ use tokio::{
     },
 };
 
+use crate::configs::{CoreConfig, ConfigurationSource};
 use crate::{services::IBackgroundService, life_time::ILifeTimeManager};
 
 use anthill_di_derive::constructor;
@@ -52,9 +53,7 @@ async fn background_service() {
     use tokio::sync::oneshot;
     use crate::{life_time::InnerStateLifeTimeManager, Application, services::BackgroundService};
 
-    let configuration_path = "background_service.json".to_string();
-
-    let mut app = Application::new(Some(configuration_path.clone())).await.unwrap();
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
 
     app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
     
@@ -66,6 +65,4 @@ async fn background_service() {
     app.register_service::<BackgroundService<TestBackgroundService2>>().await.unwrap();
     
     app.run().await.unwrap();
-
-    std::fs::remove_file(configuration_path).unwrap();
 }
\ No newline at end of file
@@ -0,0 +1,108 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::broadcast;
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::{
+    events::{HostEvent, HostEventBus},
+    services::{IBackgroundService, IOwnedBackgroundService, IBlockingService, ServiceId, ServiceState, StopToken},
+    Application,
+};
+
+use anthill_di_derive::constructor;
+
+#[derive(constructor)]
+struct TestPanicService {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestPanicService {
+    async fn execute(&self) {
+        panic!("test execute panic");
+    }
+}
+
+#[derive(constructor)]
+struct TestOwnedPanicService {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IOwnedBackgroundService for TestOwnedPanicService {
+    async fn execute(&mut self, _stop_token: &StopToken) {
+        panic!("test execute panic");
+    }
+}
+
+#[derive(constructor)]
+struct TestBlockingPanicService {}
+
+impl IBlockingService for TestBlockingPanicService {
+    fn execute(&mut self, _stop_token: &StopToken) {
+        panic!("test execute panic");
+    }
+}
+
+async fn wait_failed(events: &mut broadcast::Receiver<HostEvent>) -> ServiceId {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let HostEvent::ServiceFailed { service_id, .. } = events.recv().await.unwrap() {
+                return service_id;
+            }
+        }
+    }).await.unwrap()
+}
+
+#[tokio::test]
+async fn background_service_failed() {
+    use crate::services::BackgroundService;
+
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    let mut events = app.root_ioc_context.resolve::<Arc<HostEventBus>>().await.unwrap().subscribe();
+
+    // execute panic without supervisor switches service to failed state
+    let service_id = app.service_manager.add_service::<BackgroundService<TestPanicService>>().await.unwrap();
+    app.service_manager.start_service(service_id).await.unwrap();
+
+    assert_eq!(service_id, wait_failed(&mut events).await);
+    assert_eq!(ServiceState::Failed, app.service_manager.get_state(service_id).await.unwrap());
+}
+
+#[tokio::test]
+async fn scoped_background_service_failed() {
+    use crate::services::ScopedBackgroundService;
+
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    let mut events = app.root_ioc_context.resolve::<Arc<HostEventBus>>().await.unwrap().subscribe();
+
+    let service_id = app.service_manager.add_service::<ScopedBackgroundService<TestPanicService>>().await.unwrap();
+    app.service_manager.start_service(service_id).await.unwrap();
+
+    assert_eq!(service_id, wait_failed(&mut events).await);
+    assert_eq!(ServiceState::Failed, app.service_manager.get_state(service_id).await.unwrap());
+}
+
+#[tokio::test]
+async fn owned_background_service_failed() {
+    use crate::services::OwnedBackgroundService;
+
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    let mut events = app.root_ioc_context.resolve::<Arc<HostEventBus>>().await.unwrap().subscribe();
+
+    let service_id = app.service_manager.add_service::<OwnedBackgroundService<TestOwnedPanicService>>().await.unwrap();
+    app.service_manager.start_service(service_id).await.unwrap();
+
+    assert_eq!(service_id, wait_failed(&mut events).await);
+    assert_eq!(ServiceState::Failed, app.service_manager.get_state(service_id).await.unwrap());
+}
+
+#[tokio::test]
+async fn blocking_service_failed() {
+    use crate::services::BlockingService;
+
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    let mut events = app.root_ioc_context.resolve::<Arc<HostEventBus>>().await.unwrap().subscribe();
+
+    let service_id = app.service_manager.add_service::<BlockingService<TestBlockingPanicService>>().await.unwrap();
+    app.service_manager.start_service(service_id).await.unwrap();
+
+    assert_eq!(service_id, wait_failed(&mut events).await);
+    assert_eq!(ServiceState::Failed, app.service_manager.get_state(service_id).await.unwrap());
+}
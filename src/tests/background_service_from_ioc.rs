@@ -1,12 +1,10 @@
-use crate::{configs::CoreConfig, Application};
+use crate::{configs::CoreConfigSnapshot, Application, types::StartupResult};
 use crate::{IStartup, life_time::{ILifeTimeManager, InnerStateLifeTimeManager}};
 use std::sync::Arc;
 
 use anthill_di::{
     DependencyContext,
 };
-use anthill_di_configuration_extension::ConfigurationSnapshot;
-use anthill_di_configuration_extension::source::JsonFileConfiguration;
 use tokio::sync::oneshot;
 use tokio::{
     sync::{
@@ -57,28 +55,33 @@ struct TestStartup {}
 
 #[async_trait_with_sync::async_trait(Sync)]
 impl IStartup for TestStartup {
-    async fn configure_dependency(&mut self, root_ioc_context: &mut DependencyContext) {
+    async fn configure_dependency(&mut self, root_ioc_context: &mut DependencyContext) -> StartupResult {
         let (tx, rx) = oneshot::channel::<String>();
         
         root_ioc_context.register_instance(RwLock::new(Some(tx))).await.unwrap();
         root_ioc_context.register_instance(RwLock::new(Some(rx))).await.unwrap();
+
+        Ok(())
     }
 
-    async fn configure_application(&mut self, _core_config: Arc<RwLock<ConfigurationSnapshot<CoreConfig, JsonFileConfiguration::<CoreConfig>>>>, app: &mut Application) {
+    async fn configure_application(&mut self, _core_config: Arc<RwLock<CoreConfigSnapshot>>, app: &mut Application) -> StartupResult {
         app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
 
         app.register_service::<BackgroundService<TestBackgroundService1>>().await.unwrap();
         app.register_service::<BackgroundService<TestBackgroundService2>>().await.unwrap();
+
+        Ok(())
     }
 }
 
 #[tokio::test]
 async fn background_service_from_ioc() {
     use anthill_di::DependencyLifeCycle;
+    use anthill_di_configuration_extension::extensions::RegisterSourceExtension;
+    use crate::configs::{CoreConfig, ConfigurationSource};
 
-    let configuration_path = "app_config.json".to_string();
-
-    let root_context = DependencyContext::new_root();
+    let mut root_context = DependencyContext::new_root();
+    root_context.register_source(|_| Ok(ConfigurationSource::in_memory(&CoreConfig::default()))).await.unwrap();
     root_context.register_type::<Application>(DependencyLifeCycle::Transient).await.unwrap();
 
     let mut app = root_context.resolve::<Application>().await.unwrap();
@@ -86,6 +89,4 @@ async fn background_service_from_ioc() {
     app.register_startup::<TestStartup>().await.unwrap();
     
     app.run().await.unwrap();
-
-    std::fs::remove_file(configuration_path).unwrap();
 }
\ No newline at end of file
@@ -0,0 +1,89 @@
+use std::{sync::{Arc, Mutex}, time::Duration};
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::services::{IBlockingService, StopToken};
+
+use anthill_di_derive::constructor;
+
+#[derive(Default)]
+struct TestReport {
+    thread_name: Mutex<Option<String>>,
+    stopped_with: Mutex<Vec<u32>>,
+}
+
+#[derive(constructor)]
+struct TestBlockingService {
+    #[custom_resolve(value = "0")] ticks: u32,
+    report: Arc<TestReport>,
+}
+
+impl IBlockingService for TestBlockingService {
+    fn execute(&mut self, stop_token: &StopToken) {
+        *self.report.thread_name.lock().unwrap() = std::thread::current().name().map(str::to_string);
+
+        while !stop_token.wait_timeout(Duration::from_millis(5)) {
+            self.ticks += 1;
+        }
+    }
+
+    fn on_stop(&mut self) {
+        self.report.stopped_with.lock().unwrap().push(self.ticks);
+    }
+}
+
+#[derive(constructor)]
+struct TestPanicBlockingService {}
+
+impl IBlockingService for TestPanicBlockingService {
+    fn execute(&mut self, _stop_token: &StopToken) {
+        panic!("test blocking failure");
+    }
+}
+
+#[tokio::test]
+async fn blocking_service() {
+    use crate::{Application, services::{BlockingService, ServiceState}};
+
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.root_ioc_context.register_instance(TestReport::default()).await.unwrap();
+    let report = app.root_ioc_context.resolve::<Arc<TestReport>>().await.unwrap();
+
+    let service_id = app.service_manager.add_service::<BlockingService<TestBlockingService>>().await.unwrap();
+
+    app.service_manager.start_service(service_id).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    app.service_manager.stop_service(service_id).await.unwrap();
+
+    assert_eq!(ServiceState::Stopped, app.service_manager.get_state(service_id).await.unwrap());
+    assert_eq!(Some("TestBlockingService".to_string()), *report.thread_name.lock().unwrap());
+
+    let stopped_with = report.stopped_with.lock().unwrap().clone();
+    assert_eq!(1, stopped_with.len());
+    assert!(stopped_with[0] > 0);
+}
+
+#[tokio::test]
+async fn blocking_service_panic() {
+    use crate::{
+        Application,
+        life_time::InnerStateLifeTimeManager,
+        metrics::{MetricsRegistry, BACKGROUND_SERVICE_PANICS},
+        services::{BlockingService, CompletionMode, ExecutionStatus},
+    };
+    use anthill_di::types::TypeInfo;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<BlockingService<TestPanicBlockingService>>().await.unwrap();
+
+    let report = app.run_to_completion(CompletionMode::AllBackgroundServices).await.unwrap();
+
+    assert_eq!(1, report.completions.len());
+    assert!(matches!(report.completions[0].status, ExecutionStatus::Panicked { .. }));
+
+    let registry = app.root_ioc_context.resolve::<Arc<MetricsRegistry>>().await.unwrap();
+    let service_name = TypeInfo::from_type::<TestPanicBlockingService>().type_name;
+    assert_eq!(Some(1), registry.get_counter(BACKGROUND_SERVICE_PANICS, &[("service", service_name.as_str())]));
+}
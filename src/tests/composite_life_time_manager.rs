@@ -0,0 +1,166 @@
+use std::{
+    sync::{Arc, atomic::{AtomicBool, Ordering}},
+    time::Duration,
+};
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::{services::IBaseService, life_time::ILifeTimeManager};
+
+use anthill_di_derive::constructor;
+
+#[derive(Default)]
+struct TestTimerLifeTimeManager {
+    is_stopped: AtomicBool,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl ILifeTimeManager for TestTimerLifeTimeManager {
+    async fn stop(&self) {
+        self.is_stopped.store(true, Ordering::SeqCst)
+    }
+
+    async fn is_running(&self) -> bool {
+        !self.is_stopped.load(Ordering::SeqCst)
+    }
+
+    async fn wait_for_stop(&self) {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+}
+
+#[derive(constructor)]
+struct TestHostedService {
+    application_life_time: Arc<dyn ILifeTimeManager>,
+    #[custom_resolve(value = "AtomicBool::new(false)")] is_stopped: AtomicBool,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestHostedService {
+    async fn on_start(&mut self) {
+        assert!(self.application_life_time.is_running().await);
+    }
+
+    async fn on_stop(&mut self) {
+        self.is_stopped.store(!self.application_life_time.is_running().await, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn composite_life_time_manager() {
+    use crate::{
+        Application,
+        life_time::{InnerStateLifeTimeManager, CompositeLifeTimeManager, CompositeLifeTimeManagerBuilder},
+    };
+    use anthill_di::types::TypeInfo;
+    use tokio::sync::RwLock;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_composite_life_time_manager(CompositeLifeTimeManagerBuilder::new()
+        .add_life_time_manager::<InnerStateLifeTimeManager>()
+        .add_life_time_manager_instance(TestTimerLifeTimeManager::default())
+    ).await.unwrap();
+
+    app.register_service::<TestHostedService>().await.unwrap();
+
+    app.run().await.unwrap();
+
+    let life_time_manager = app.root_ioc_context.resolve::<Arc<CompositeLifeTimeManager>>().await.unwrap();
+    assert_eq!(Some(TypeInfo::from_type::<TestTimerLifeTimeManager>()), life_time_manager.get_stop_trigger());
+
+    // stop propagated to all children
+    assert!(!life_time_manager.is_running().await);
+
+    let service = app.root_ioc_context.resolve::<Arc<RwLock<TestHostedService>>>().await.unwrap();
+    assert!(service.read().await.is_stopped.load(Ordering::SeqCst));
+}
+
+#[derive(Default)]
+struct TestAbortWaitState {
+    is_waiting: AtomicBool,
+}
+
+struct TestAbortWaitGuard(Arc<TestAbortWaitState>);
+
+impl Drop for TestAbortWaitGuard {
+    fn drop(&mut self) {
+        self.0.is_waiting.store(false, Ordering::SeqCst);
+    }
+}
+
+struct TestAbortWaitLifeTimeManager {
+    state: Arc<TestAbortWaitState>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl ILifeTimeManager for TestAbortWaitLifeTimeManager {
+    async fn stop(&self) {}
+
+    async fn is_running(&self) -> bool {
+        true
+    }
+
+    async fn wait_for_stop(&self) {
+        std::future::pending::<()>().await;
+    }
+
+    async fn wait_for_abort(&self) {
+        self.state.is_waiting.store(true, Ordering::SeqCst);
+        let _guard = TestAbortWaitGuard(self.state.clone());
+        std::future::pending::<()>().await;
+    }
+}
+
+#[tokio::test]
+async fn composite_life_time_manager_wait_dropped() {
+    use crate::life_time::{CompositeLifeTimeManager, CompositeLifeTimeManagerBuilder};
+    use anthill_di::DependencyContext;
+
+    let state = Arc::new(TestAbortWaitState::default());
+
+    let builder = CompositeLifeTimeManagerBuilder::new()
+        .add_life_time_manager_instance(TestAbortWaitLifeTimeManager { state: state.clone() });
+    let life_time_manager = CompositeLifeTimeManager::build(builder, DependencyContext::new_root()).await.unwrap();
+
+    // dropped like losing branch of select
+    assert!(tokio::time::timeout(Duration::from_millis(20), life_time_manager.wait_for_abort()).await.is_err());
+    assert!(state.is_waiting.load(Ordering::SeqCst));
+
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+
+    assert!(!state.is_waiting.load(Ordering::SeqCst));
+}
+
+struct TestPanicWaitLifeTimeManager;
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl ILifeTimeManager for TestPanicWaitLifeTimeManager {
+    async fn stop(&self) {}
+
+    async fn is_running(&self) -> bool {
+        true
+    }
+
+    async fn wait_for_stop(&self) {
+        panic!("test wait panic");
+    }
+}
+
+#[tokio::test]
+async fn composite_life_time_manager_wait_panicked() {
+    use crate::life_time::{CompositeLifeTimeManager, CompositeLifeTimeManagerBuilder};
+    use anthill_di::{DependencyContext, types::TypeInfo};
+
+    let builder = CompositeLifeTimeManagerBuilder::new()
+        .add_life_time_manager_instance(TestAbortWaitLifeTimeManager { state: Arc::new(TestAbortWaitState::default()) })
+        .add_life_time_manager_instance(TestPanicWaitLifeTimeManager);
+    let life_time_manager = CompositeLifeTimeManager::build(builder, DependencyContext::new_root()).await.unwrap();
+
+    // panicked child wait stops composite
+    tokio::time::timeout(Duration::from_secs(1), life_time_manager.wait_for_stop()).await.unwrap();
+
+    assert_eq!(Some(TypeInfo::from_type::<TestPanicWaitLifeTimeManager>()), life_time_manager.get_stop_trigger());
+    assert!(!life_time_manager.is_running().await);
+}
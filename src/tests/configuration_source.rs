@@ -0,0 +1,56 @@
+use std::{sync::Arc, time::Duration};
+
+use serde::{Deserialize, Serialize};
+use tokio::sync::RwLock;
+
+use crate::{
+    configs::{CoreConfig, ConfigurationSource, ConfigSnapshot, InMemoryConfiguration},
+    services::IBaseService,
+    life_time::ILifeTimeManager,
+};
+
+use anthill_di_derive::constructor;
+
+#[derive(Deserialize, Serialize, Default, Clone)]
+struct TestConfig {
+    #[serde(default)]
+    processed: u32,
+}
+
+#[derive(constructor)]
+struct TestHostedService {
+    config: Arc<RwLock<ConfigSnapshot<TestConfig>>>,
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestHostedService {
+    async fn on_start(&mut self) {
+        self.config.write().await.value.processed += 1;
+        self.application_life_time.stop().await;
+    }
+
+    async fn on_stop(&mut self) {
+        self.config.read().await.store().await.unwrap();
+    }
+}
+
+#[tokio::test]
+async fn in_memory_configuration() {
+    use crate::{Application, life_time::InnerStateLifeTimeManager};
+
+    let core_config_source = InMemoryConfiguration::new(&CoreConfig::default());
+    let test_config_source = InMemoryConfiguration::new(&TestConfig { processed: 41 });
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::InMemory(core_config_source.clone())).await.unwrap();
+    app.core_config.write().await.value.drain_timeout = Duration::from_millis(100);
+
+    app.register_configuration(ConfigurationSource::InMemory(test_config_source.clone())).await.unwrap();
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<TestHostedService>().await.unwrap();
+
+    app.run().await.unwrap();
+
+    assert_eq!(42, test_config_source.get_value().unwrap().processed);
+    assert_eq!(Duration::from_millis(100), core_config_source.get_value().unwrap().drain_timeout);
+}
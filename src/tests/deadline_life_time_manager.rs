@@ -0,0 +1,105 @@
+use std::{sync::Arc, time::{Duration, Instant}};
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::{services::IBackgroundService, life_time::IdleLifeTimeManager};
+
+use anthill_di_derive::constructor;
+
+#[derive(constructor)]
+struct TestActiveService {
+    idle_life_time: Arc<IdleLifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestActiveService {
+    async fn execute(&self) {
+        for _ in 0..3 {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            self.idle_life_time.report_activity();
+        }
+    }
+}
+
+#[tokio::test]
+async fn deadline_life_time_manager() {
+    use crate::{Application, life_time::DeadlineLifeTimeManager};
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    let started_at = Instant::now();
+    app.register_life_time_manager_instance(DeadlineLifeTimeManager::after(Duration::from_millis(100), app.clock.clone())).await.unwrap();
+
+    app.run().await.unwrap();
+
+    assert!(started_at.elapsed() >= Duration::from_millis(100));
+}
+
+#[tokio::test]
+async fn idle_life_time_manager() {
+    use crate::{Application, services::BackgroundService};
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    let started_at = Instant::now();
+    app.register_life_time_manager_instance(IdleLifeTimeManager::new(Duration::from_millis(100), app.clock.clone())).await.unwrap();
+    app.register_service::<BackgroundService<TestActiveService>>().await.unwrap();
+
+    app.run().await.unwrap();
+
+    // last activity after 150ms, then idle for 100ms
+    assert!(started_at.elapsed() >= Duration::from_millis(250));
+}
+
+#[tokio::test]
+async fn deadline_life_time_manager_at() {
+    use std::time::SystemTime;
+    use crate::{Application, life_time::{DeadlineLifeTimeManager, ILifeTimeManager}, time::ManualClock};
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    let clock = ManualClock::new();
+    app.clock.set_clock(clock.clone());
+
+    // already passed deadline
+    assert!(!DeadlineLifeTimeManager::at(SystemTime::now() - Duration::from_secs(1), app.clock.clone()).is_running().await);
+
+    app.register_life_time_manager_instance(DeadlineLifeTimeManager::at(SystemTime::now() + Duration::from_secs(60), app.clock.clone())).await.unwrap();
+
+    let run_task = tokio::spawn(async move { app.run().await });
+
+    // deadline reached only when time moved
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!run_task.is_finished());
+
+    clock.advance(Duration::from_secs(60));
+
+    run_task.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn idle_life_time_manager_composite() {
+    use crate::{
+        Application,
+        life_time::{CompositeLifeTimeManager, CompositeLifeTimeManagerBuilder, InnerStateLifeTimeManager},
+        services::BackgroundService,
+    };
+    use anthill_di::types::TypeInfo;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    let started_at = Instant::now();
+    app.root_ioc_context.register_instance(IdleLifeTimeManager::new(Duration::from_millis(100), app.clock.clone())).await.unwrap();
+    app.register_composite_life_time_manager(CompositeLifeTimeManagerBuilder::new()
+        .add_life_time_manager::<InnerStateLifeTimeManager>()
+        .add_shared_life_time_manager::<IdleLifeTimeManager>()
+    ).await.unwrap();
+    app.register_service::<BackgroundService<TestActiveService>>().await.unwrap();
+
+    app.run().await.unwrap();
+
+    // activity reported to composite child
+    assert!(started_at.elapsed() >= Duration::from_millis(250));
+
+    let life_time_manager = app.root_ioc_context.resolve::<Arc<CompositeLifeTimeManager>>().await.unwrap();
+    assert_eq!(Some(TypeInfo::from_type::<IdleLifeTimeManager>()), life_time_manager.get_stop_trigger());
+}
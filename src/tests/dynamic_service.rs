@@ -0,0 +1,147 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use tokio::sync::oneshot::{self, Sender};
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::{
+    services::{IBaseService, IBackgroundService, ServiceManager, ServiceState},
+    life_time::ILifeTimeManager,
+};
+
+use anthill_di_derive::constructor;
+
+struct TestTenantService {
+    tenant: String,
+    sender: Option<Sender<String>>,
+    stop_counter: Arc<AtomicUsize>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestTenantService {
+    async fn on_start(&mut self) {
+        self.sender.take().unwrap().send(self.tenant.clone()).unwrap();
+    }
+
+    async fn on_stop(&mut self) {
+        self.stop_counter.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(constructor)]
+struct TestIocService {
+    #[custom_resolve(value = "Arc::new(AtomicUsize::new(0))")] start_counter: Arc<AtomicUsize>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestIocService {
+    async fn on_start(&mut self) {
+        self.start_counter.fetch_add(1, Ordering::SeqCst);
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+#[derive(constructor)]
+struct TestTenantLoaderService {
+    service_manager: Arc<ServiceManager>,
+    application_life_time: Arc<dyn ILifeTimeManager>,
+    #[custom_resolve(value = "Arc::new(AtomicUsize::new(0))")] stop_counter: Arc<AtomicUsize>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestTenantLoaderService {
+    async fn execute(&self) {
+        let mut tenant_service_ids = Vec::new();
+
+        for tenant in ["tenant_1", "tenant_2"] {
+            let (tx, rx) = oneshot::channel::<String>();
+
+            let service_id = self.service_manager.add_service_instance(TestTenantService {
+                tenant: tenant.to_string(),
+                sender: Some(tx),
+                stop_counter: self.stop_counter.clone(),
+            }).await;
+
+            assert_eq!(ServiceState::Pending, self.service_manager.get_state(service_id).await.unwrap());
+
+            self.service_manager.start_service(service_id).await.unwrap();
+
+            assert_eq!(tenant.to_string(), rx.await.unwrap());
+            assert_eq!(ServiceState::Running, self.service_manager.get_state(service_id).await.unwrap());
+
+            tenant_service_ids.push(service_id);
+        }
+
+        self.service_manager.remove_service(tenant_service_ids[0]).await.unwrap();
+        assert_eq!(1, self.stop_counter.load(Ordering::SeqCst));
+        assert!(self.service_manager.get_state(tenant_service_ids[0]).await.is_err());
+
+        let ioc_service_id = self.service_manager.add_service::<TestIocService>().await.unwrap();
+        self.service_manager.start_service(ioc_service_id).await.unwrap();
+        assert_eq!(ServiceState::Running, self.service_manager.get_state(ioc_service_id).await.unwrap());
+
+        self.application_life_time.stop().await;
+    }
+}
+
+#[tokio::test]
+async fn dynamic_service() {
+    use crate::{Application, life_time::InnerStateLifeTimeManager, services::BackgroundService};
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<BackgroundService<TestTenantLoaderService>>().await.unwrap();
+
+    app.run().await.unwrap();
+
+    let service_ids = app.service_manager.get_service_ids().await;
+    assert_eq!(3, service_ids.len());
+
+    for service_id in service_ids {
+        assert_eq!(ServiceState::Stopped, app.service_manager.get_state(service_id).await.unwrap());
+    }
+}
+
+struct TestSlowStartService {
+    start_receiver: Option<oneshot::Receiver<()>>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestSlowStartService {
+    async fn on_start(&mut self) {
+        self.start_receiver.take().unwrap().await.unwrap();
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+#[tokio::test]
+async fn dynamic_service_remove_starting() {
+    use crate::{Application, types::ServiceManagerError};
+
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    let (tx, rx) = oneshot::channel();
+    let service_id = app.service_manager.add_service_instance(TestSlowStartService { start_receiver: Some(rx) }).await;
+
+    let mut state_changes = app.service_manager.subscribe_state_changes();
+    let service_manager = app.service_manager.clone();
+    let start_task = tokio::spawn(async move { service_manager.start_service(service_id).await });
+
+    while state_changes.recv().await.unwrap().state != ServiceState::Starting {}
+
+    assert_eq!(
+        Err(ServiceManagerError::InvalidServiceState { service_id, state: ServiceState::Starting }),
+        app.service_manager.remove_service(service_id).await,
+    );
+
+    tx.send(()).unwrap();
+    start_task.await.unwrap().unwrap();
+
+    app.service_manager.remove_service(service_id).await.unwrap();
+    assert!(app.service_manager.get_state(service_id).await.is_err());
+}
@@ -0,0 +1,80 @@
+use std::{sync::Arc, time::Duration};
+
+use tokio::sync::RwLock;
+
+use crate::{configs::CoreConfigSnapshot, life_time::ILifeTimeManager, services::IBaseService, types::StartupResult, Application, IStartup};
+
+use anthill_di::DependencyContext;
+use anthill_di_derive::constructor;
+
+#[derive(constructor)]
+struct TestHostedService {
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestHostedService {
+    async fn on_start(&mut self) {
+        self.application_life_time.stop().await;
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+#[derive(constructor)]
+struct TestStartup {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IStartup for TestStartup {
+    async fn configure_dependency(&mut self, _root_ioc_context: &mut DependencyContext) -> StartupResult {
+        Ok(())
+    }
+
+    async fn configure_application(&mut self, _core_config: Arc<RwLock<CoreConfigSnapshot>>, app: &mut Application) -> StartupResult {
+        app.register_service::<TestHostedService>().await.unwrap();
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn host_event_bus() {
+    use crate::{
+        configs::{CoreConfig, ConfigurationSource},
+        events::{HostEvent, HostEventBus, StopReason},
+        life_time::InnerStateLifeTimeManager,
+    };
+    use anthill_di::types::TypeInfo;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_startup::<TestStartup>().await.unwrap();
+
+    let mut events = app.root_ioc_context.resolve::<Arc<HostEventBus>>().await.unwrap().subscribe();
+
+    app.run().await.unwrap();
+
+    app.core_config.write().await.value.drain_timeout = Duration::from_millis(100);
+    app.reload_configuration::<CoreConfig>().await.unwrap();
+    assert_eq!(CoreConfig::default().drain_timeout, app.core_config.read().await.value.drain_timeout);
+
+    let mut received = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        received.push(event);
+    }
+
+    let service_id = app.service_manager.get_service_ids().await[0];
+    let service_type_info = TypeInfo::from_type::<TestHostedService>();
+
+    assert_eq!(vec![
+        HostEvent::StartupApplied { startup_type_info: TypeInfo::from_type::<TestStartup>() },
+        HostEvent::ServiceRegistered { service_id, service_type_info: service_type_info.clone() },
+        HostEvent::ServiceStarting { service_id, service_type_info: service_type_info.clone() },
+        HostEvent::ServiceStarted { service_id, service_type_info: service_type_info.clone() },
+        HostEvent::ApplicationStarted,
+        HostEvent::StopRequested { reason: StopReason::LifeTimeManager },
+        HostEvent::ServiceStopping { service_id, service_type_info: service_type_info.clone() },
+        HostEvent::ServiceStopped { service_id, service_type_info },
+        HostEvent::ApplicationStopped,
+        HostEvent::ConfigurationReloaded { configuration_type_info: TypeInfo::from_type::<CoreConfig>() },
+    ], received);
+}
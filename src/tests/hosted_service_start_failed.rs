@@ -0,0 +1,106 @@
+use std::sync::{Arc, atomic::{AtomicBool, Ordering}};
+
+use anthill_di::{
+    DependencyContext,
+    Constructor,
+    types::BuildDependencyResult,
+};
+
+use crate::services::IBaseService;
+
+struct TestStopped(AtomicBool);
+
+struct TestPanicService {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for TestPanicService {
+    async fn ctor(_: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self {})
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestPanicService {
+    async fn on_start(&mut self) {
+        panic!("test start panic");
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+struct TestHostedService {
+    stopped: Arc<TestStopped>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for TestHostedService {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self { stopped: ctx.resolve().await? })
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestHostedService {
+    async fn on_start(&mut self) {}
+
+    async fn on_stop(&mut self) {
+        self.stopped.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn hosted_service_start_failed() {
+    use crate::{
+        Application,
+        configs::{CoreConfig, ConfigurationSource},
+        events::{HostEvent, HostEventBus},
+        life_time::InnerStateLifeTimeManager,
+        services::ServiceState,
+        types::AppRunError,
+    };
+    use anthill_di::types::TypeInfo;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.root_ioc_context.register_instance(TestStopped(AtomicBool::new(false))).await.unwrap();
+
+    app.register_service::<TestPanicService>().await.unwrap();
+    app.register_service::<TestHostedService>().await.unwrap();
+
+    let mut events = app.root_ioc_context.resolve::<Arc<HostEventBus>>().await.unwrap().subscribe();
+
+    let result = app.run().await;
+
+    assert_eq!(result.err(), Some(AppRunError::ServiceStartFailed {
+        service_type_info: TypeInfo::from_type::<TestPanicService>(),
+        message: String::new(),
+    }));
+
+    // started services stopped, none left starting
+    assert!(app.root_ioc_context.resolve::<Arc<TestStopped>>().await.unwrap().0.load(Ordering::SeqCst));
+
+    let mut panic_service_id = None;
+    for service_id in app.service_manager.get_service_ids().await {
+        let expected = if app.service_manager.get_type_info(service_id).await.unwrap() == TypeInfo::from_type::<TestPanicService>() {
+            panic_service_id = Some(service_id);
+            // failed service aborted with remaining tasks
+            ServiceState::Aborted
+        } else {
+            ServiceState::Stopped
+        };
+
+        assert_eq!(expected, app.service_manager.get_state(service_id).await.unwrap());
+    }
+
+    let mut received = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        received.push(event);
+    }
+
+    assert!(received.contains(&HostEvent::ServiceFailed { service_id: panic_service_id.unwrap(), service_type_info: TypeInfo::from_type::<TestPanicService>() }));
+    assert!(!received.contains(&HostEvent::ApplicationStarted));
+
+    // failed start stopped like regular one
+    assert_eq!(Some(&HostEvent::ApplicationStopped), received.last());
+}
@@ -0,0 +1,71 @@
+use std::sync::Arc;
+
+use anthill_di::{
+    DependencyContext,
+    Constructor,
+    types::BuildDependencyResult,
+};
+
+use crate::{services::IBaseService, life_time::ILifeTimeManager};
+
+struct TestPanicService {
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for TestPanicService {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        Ok(Self { application_life_time: ctx.resolve().await? })
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestPanicService {
+    async fn on_start(&mut self) {
+        self.application_life_time.stop().await;
+    }
+
+    async fn on_stop(&mut self) {
+        panic!("test stop panic");
+    }
+}
+
+#[tokio::test]
+async fn hosted_service_stop_failed() {
+    use crate::{
+        Application,
+        configs::{CoreConfig, ConfigurationSource},
+        events::{HostEvent, HostEventBus},
+        life_time::InnerStateLifeTimeManager,
+        services::ServiceState,
+        types::AppRunError,
+    };
+    use anthill_di::types::TypeInfo;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<TestPanicService>().await.unwrap();
+
+    let mut events = app.root_ioc_context.resolve::<Arc<HostEventBus>>().await.unwrap().subscribe();
+
+    let result = app.run().await;
+
+    assert_eq!(result.err(), Some(AppRunError::ServiceStopFailed {
+        service_type_info: TypeInfo::from_type::<TestPanicService>(),
+        message: String::new(),
+    }));
+
+    let service_id = app.service_manager.get_service_ids().await[0];
+
+    // failed service aborted with remaining tasks
+    assert_eq!(ServiceState::Aborted, app.service_manager.get_state(service_id).await.unwrap());
+
+    let mut received = Vec::new();
+    while let Ok(event) = events.try_recv() {
+        received.push(event);
+    }
+
+    assert!(received.contains(&HostEvent::ServiceFailed { service_id, service_type_info: TypeInfo::from_type::<TestPanicService>() }));
+    assert!(!received.contains(&HostEvent::ServiceStopped { service_id, service_type_info: TypeInfo::from_type::<TestPanicService>() }));
+}
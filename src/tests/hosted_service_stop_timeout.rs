@@ -16,6 +16,7 @@ use tokio::{
     task::JoinHandle, time,
 };
 
+use crate::configs::{CoreConfig, ConfigurationSource};
 use crate::{services::IBaseService, life_time::ILifeTimeManager};
 
 struct TestHostedService1 {
@@ -83,7 +84,8 @@ impl IBaseService for TestHostedService2 {
     }
 }
 
-#[tokio::test]
+// paused time auto-advanced, timeouts expire without real waiting
+#[tokio::test(start_paused = true)]
 async fn hosted_service_stop_timeout() {
     use crate::{
         Application,
@@ -98,9 +100,7 @@ async fn hosted_service_stop_timeout() {
         },
     };
 
-    let configuration_path = "hosted_service_stop_timeout.json".to_string();
-
-    let mut app = Application::new(Some(configuration_path.clone())).await.unwrap();
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
     app.core_config.write().await.value.on_stop_timeout = Duration::from_secs(6);
 
     app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
@@ -114,8 +114,6 @@ async fn hosted_service_stop_timeout() {
 
     let result = app.run().await;
 
-    std::fs::remove_file(configuration_path).unwrap();
-
     assert_eq!(result.err(), Some(AppRunError::ServiceStopTimeoutExpired {
         timeout_duration: Duration::from_millis(6000),
         service_type_info: TypeInfo::from_type::<TestHostedService2>(),
@@ -0,0 +1,47 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::{services::IBaseService, time::{Clock, ManualClock}};
+
+use anthill_di_derive::constructor;
+
+#[derive(constructor)]
+struct TestSlowStartService {
+    clock: Arc<Clock>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestSlowStartService {
+    async fn on_start(&mut self) {
+        self.clock.sleep(Duration::from_secs(60)).await;
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+#[tokio::test]
+async fn manual_clock() {
+    use crate::{Application, life_time::InnerStateLifeTimeManager, types::AppRunError};
+    use anthill_di::types::TypeInfo;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    let clock = ManualClock::new();
+    app.clock.set_clock(clock.clone());
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<TestSlowStartService>().await.unwrap();
+
+    let run_task = tokio::spawn(async move { app.run().await });
+
+    // start timeout expires only when time moved
+    tokio::time::sleep(Duration::from_millis(50)).await;
+    assert!(!run_task.is_finished());
+
+    clock.advance(Duration::from_millis(5000));
+
+    assert_eq!(run_task.await.unwrap().err(), Some(AppRunError::ServiceStartTimeoutExpired {
+        timeout_duration: Duration::from_millis(5000),
+        service_type_info: TypeInfo::from_type::<TestSlowStartService>(),
+    }));
+}
@@ -0,0 +1,105 @@
+use std::{
+    sync::{Arc, atomic::{AtomicUsize, Ordering}},
+    time::Duration,
+};
+
+use crate::services::{IBaseService, IBackgroundService};
+
+use anthill_di_derive::constructor;
+
+#[derive(constructor)]
+struct TestFailingOnceService {
+    #[custom_resolve(value = "Arc::new(AtomicUsize::new(0))")] executions: Arc<AtomicUsize>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestFailingOnceService {
+    async fn execute(&self) {
+        if self.executions.fetch_add(1, Ordering::SeqCst) == 0 {
+            panic!("test failure");
+        }
+    }
+}
+
+#[tokio::test]
+async fn metrics() {
+    use crate::{
+        metrics::{MetricsRegistry, SUPERVISOR_RESTARTS, BACKGROUND_SERVICE_PANICS, SERVICE_START_DURATION, SERVICE_STATE, WAIT_FOR_STOP_DURATION},
+        services::{BackgroundService, Supervisor, SupervisorBuilder, ServiceState},
+        testing::TestHost,
+    };
+    use anthill_di::types::TypeInfo;
+
+    let mut host = TestHost::new().await.unwrap();
+
+    host.get_app().register_supervisor(SupervisorBuilder::new("workers")
+        .add_service::<BackgroundService<TestFailingOnceService>>()
+    ).await;
+
+    let registry = host.get_app().root_ioc_context.resolve::<Arc<MetricsRegistry>>().await.unwrap();
+
+    host.start();
+
+    let service_name = TypeInfo::from_type::<TestFailingOnceService>().type_name;
+    let restarts_labels = [("supervisor", "workers"), ("service", service_name.as_str())];
+
+    tokio::time::timeout(Duration::from_secs(1), async {
+        while registry.get_counter(SUPERVISOR_RESTARTS, &restarts_labels).is_none() {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }).await.unwrap();
+
+    host.stop().await.unwrap();
+
+    assert_eq!(Some(1), registry.get_counter(SUPERVISOR_RESTARTS, &restarts_labels));
+    assert_eq!(Some(1), registry.get_counter(BACKGROUND_SERVICE_PANICS, &[("service", service_name.as_str())]));
+
+    let service_manager = host.get_service_manager();
+    let supervisor_name = service_manager.get_type_info(service_manager.get_service_ids().await[0]).await.unwrap().type_name;
+    assert_eq!(Some(1), registry.get_duration_count(SERVICE_START_DURATION, &[("service", supervisor_name.as_str())]));
+    assert_eq!(Some(1.0), registry.get_gauge(SERVICE_STATE, &[("service_id", "0"), ("service", supervisor_name.as_str()), ("state", &format!("{:?}", ServiceState::Stopped))]));
+    assert_eq!(Some(0.0), registry.get_gauge(SERVICE_STATE, &[("service_id", "0"), ("service", supervisor_name.as_str()), ("state", &format!("{:?}", ServiceState::Running))]));
+    assert_eq!(Some(1), registry.get_duration_count(WAIT_FOR_STOP_DURATION, &[]));
+
+    let output = registry.render_prometheus();
+    assert!(output.contains(&format!("# TYPE {SUPERVISOR_RESTARTS} counter\n{SUPERVISOR_RESTARTS}{{supervisor=\"workers\",service=\"{service_name}\"}} 1\n")));
+    assert!(output.contains(&format!("# TYPE {WAIT_FOR_STOP_DURATION} summary\n")));
+    assert!(supervisor_name.starts_with(std::any::type_name::<Supervisor>()));
+}
+
+struct TestIdleService;
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestIdleService {
+    async fn on_start(&mut self) {}
+
+    async fn on_stop(&mut self) {}
+}
+
+#[tokio::test]
+async fn metrics_dynamic_service() {
+    use crate::{
+        configs::{CoreConfig, ConfigurationSource},
+        metrics::{MetricsRegistry, SERVICE_RESTARTS, SERVICE_STATE},
+        services::ServiceState,
+        Application,
+    };
+
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    let registry = app.root_ioc_context.resolve::<Arc<MetricsRegistry>>().await.unwrap();
+
+    let service_id = app.service_manager.add_service_instance(TestIdleService).await;
+    let service_name = app.service_manager.get_type_info(service_id).await.unwrap().type_name;
+    let running_labels = [("service_id", "0"), ("service", service_name.as_str()), ("state", &format!("{:?}", ServiceState::Running))];
+
+    app.service_manager.start_service(service_id).await.unwrap();
+    app.service_manager.restart_service(service_id).await.unwrap();
+
+    assert_eq!(Some(1), registry.get_counter(SERVICE_RESTARTS, &[("service", service_name.as_str())]));
+    assert_eq!(Some(1.0), registry.get_gauge(SERVICE_STATE, &running_labels));
+
+    // state series of removed service not rendered anymore
+    app.service_manager.remove_service(service_id).await.unwrap();
+    assert_eq!(None, registry.get_gauge(SERVICE_STATE, &running_labels));
+    assert!(!registry.render_prometheus().contains("service_id=\"0\""));
+}
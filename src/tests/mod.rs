@@ -1,6 +1,39 @@
 pub (crate) mod hosted_service;
 pub (crate) mod hosted_service_start_timeout;
+pub (crate) mod hosted_service_start_failed;
 pub (crate) mod hosted_service_stop_timeout;
+pub (crate) mod hosted_service_stop_failed;
 pub (crate) mod background_service;
+pub (crate) mod background_service_failed;
 pub (crate) mod background_service_with_startup;
-pub (crate) mod background_service_from_ioc;
\ No newline at end of file
+pub (crate) mod background_service_from_ioc;
+pub (crate) mod dynamic_service;
+pub (crate) mod supervisor;
+pub (crate) mod startup_order;
+pub (crate) mod application_hooks;
+pub (crate) mod shutdown_phases;
+pub (crate) mod composite_life_time_manager;
+pub (crate) mod deadline_life_time_manager;
+pub (crate) mod run_to_completion;
+pub (crate) mod test_host;
+pub (crate) mod configuration_source;
+pub (crate) mod manual_clock;
+pub (crate) mod metrics;
+pub (crate) mod host_event_bus;
+pub (crate) mod scoped_background_service;
+pub (crate) mod owned_background_service;
+pub (crate) mod blocking_service;
+pub (crate) mod run_blocking;
+pub (crate) mod pid_file;
+pub (crate) mod stall_watchdog;
+pub (crate) mod task_spawner;
+#[cfg(unix)]
+pub (crate) mod process_service;
+#[cfg(unix)]
+pub (crate) mod systemd_notify;
+#[cfg(unix)]
+pub (crate) mod socket_activation;
+#[cfg(feature = "tracing")]
+pub (crate) mod tracing_spans;
+#[cfg(feature = "macros")]
+pub (crate) mod auto_registration;
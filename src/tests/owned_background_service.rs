@@ -0,0 +1,52 @@
+use std::sync::{Arc, Mutex};
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::services::{IOwnedBackgroundService, StopToken};
+
+use anthill_di_derive::constructor;
+
+#[derive(Default)]
+struct TestReport {
+    stopped_with: Mutex<Vec<u32>>,
+}
+
+#[derive(constructor)]
+struct TestCounterService {
+    #[custom_resolve(value = "0")] executions: u32,
+    report: Arc<TestReport>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IOwnedBackgroundService for TestCounterService {
+    async fn execute(&mut self, stop_token: &StopToken) {
+        self.executions += 1;
+
+        // runs until service stop
+        stop_token.wait_stop().await;
+    }
+
+    async fn on_stop(&mut self) {
+        self.report.stopped_with.lock().unwrap().push(self.executions);
+    }
+}
+
+#[tokio::test]
+async fn owned_background_service() {
+    use crate::{Application, services::{OwnedBackgroundService, ServiceState}};
+
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.root_ioc_context.register_instance(TestReport::default()).await.unwrap();
+    let report = app.root_ioc_context.resolve::<Arc<TestReport>>().await.unwrap();
+
+    let service_id = app.service_manager.add_service::<OwnedBackgroundService<TestCounterService>>().await.unwrap();
+
+    // state returned on stop and moved into execute again on restart
+    for _ in 0..2 {
+        app.service_manager.start_service(service_id).await.unwrap();
+        app.service_manager.stop_service(service_id).await.unwrap();
+    }
+
+    assert_eq!(ServiceState::Stopped, app.service_manager.get_state(service_id).await.unwrap());
+    assert_eq!(vec![1, 2], *report.stopped_with.lock().unwrap());
+}
@@ -0,0 +1,75 @@
+use std::sync::Arc;
+
+use crate::{
+    configs::{CoreConfig, ConfigurationSource},
+    life_time::ILifeTimeManager,
+    services::IBaseService,
+    types::{AppRunError, PidFileError},
+    Application,
+    PidFile,
+};
+
+use anthill_di_derive::constructor;
+
+fn pid_file_path(name: &str) -> String {
+    std::env::temp_dir().join(format!("anthill-service-system-{name}-{pid}.pid", pid = std::process::id())).to_string_lossy().to_string()
+}
+
+#[derive(constructor)]
+struct TestHostedService {
+    application_life_time: Arc<dyn ILifeTimeManager>,
+    #[custom_resolve(value = "None")] pid_file: Option<String>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestHostedService {
+    async fn on_start(&mut self) {
+        self.pid_file = Some(std::fs::read_to_string(pid_file_path("app")).unwrap());
+        self.application_life_time.stop().await;
+    }
+
+    async fn on_stop(&mut self) {
+        assert_eq!(Some(format!("{}\n", std::process::id())), self.pid_file);
+    }
+}
+
+#[test]
+fn pid_file_lock() {
+    let path = pid_file_path("lock");
+
+    // left by dead instance
+    std::fs::write(&path, "4194305\n").unwrap();
+
+    let mut pid_file = PidFile::acquire(&path).unwrap();
+    assert_eq!(format!("{}\n", std::process::id()), std::fs::read_to_string(&path).unwrap());
+
+    #[cfg(unix)]
+    assert_eq!(Err(PidFileError::AlreadyLocked { pid_file: path.clone(), pid: Some(std::process::id()) }), PidFile::acquire(&path).map(|_| ()));
+
+    pid_file.release();
+    assert!(!std::path::Path::new(&path).exists());
+}
+
+#[tokio::test]
+async fn pid_file_application() {
+    use crate::life_time::InnerStateLifeTimeManager;
+
+    let path = pid_file_path("app");
+    let core_config = CoreConfig { pid_file: Some(path.clone()), ..Default::default() };
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&core_config)).await.unwrap();
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<TestHostedService>().await.unwrap();
+
+    #[cfg(unix)]
+    {
+        let _running_instance = PidFile::acquire(&path).unwrap();
+        let mut second_app = Application::with_configuration_source(ConfigurationSource::in_memory(&core_config)).await.unwrap();
+        second_app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+        assert_eq!(Err(AppRunError::AlreadyRunning { pid_file: path.clone(), pid: Some(std::process::id()) }), second_app.run().await);
+    }
+
+    app.run().await.unwrap();
+
+    assert!(!std::path::Path::new(&path).exists());
+}
@@ -0,0 +1,109 @@
+use std::time::{Duration, Instant};
+
+use tokio::sync::broadcast;
+
+use crate::{
+    configs::{CoreConfig, ConfigurationSource, ProcessConfig, ProcessRestartPolicy},
+    events::HostEvent,
+    services::{ProcessService, ServiceState},
+    Application,
+};
+
+fn shell(name: &str, script: &str) -> ProcessConfig {
+    ProcessConfig {
+        name: Some(name.to_string()),
+        args: vec!["-c".to_string(), script.to_string()],
+        restart_delay: Duration::from_millis(10),
+        ..ProcessConfig::new("sh")
+    }
+}
+
+async fn next_process_event(events: &mut broadcast::Receiver<HostEvent>) -> HostEvent {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            match events.recv().await.unwrap() {
+                HostEvent::ProcessStarted { name, .. } => return HostEvent::ProcessStarted { name, pid: None },
+                event @ HostEvent::ProcessExited { .. } => return event,
+                _ => {},
+            }
+        }
+    }).await.unwrap()
+}
+
+#[tokio::test]
+async fn process_service_restart() {
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    let mut events = app.event_bus.subscribe();
+
+    let config = ProcessConfig { restart_policy: ProcessRestartPolicy::OnFailure, max_restarts: Some(1), ..shell("failing", "echo output; exit 3") };
+    let service_id = app.service_manager.add_service_instance(ProcessService::new(config, &app.service_manager)).await;
+    app.service_manager.start_service(service_id).await.unwrap();
+
+    let started = HostEvent::ProcessStarted { name: "failing".to_string(), pid: None };
+    assert_eq!(started, next_process_event(&mut events).await);
+    assert_eq!(HostEvent::ProcessExited { name: "failing".to_string(), exit_code: Some(3), restart: true }, next_process_event(&mut events).await);
+    assert_eq!(started, next_process_event(&mut events).await);
+    assert_eq!(HostEvent::ProcessExited { name: "failing".to_string(), exit_code: Some(3), restart: false }, next_process_event(&mut events).await);
+
+    // restarts exhausted, failure reported to service manager
+    let failed_service_id = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let HostEvent::ServiceFailed { service_id, .. } = events.recv().await.unwrap() {
+                return service_id;
+            }
+        }
+    }).await.unwrap();
+
+    assert_eq!(service_id, failed_service_id);
+    assert_eq!(ServiceState::Failed, app.service_manager.get_state(service_id).await.unwrap());
+}
+
+#[tokio::test]
+async fn process_service_stop() {
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    let mut events = app.event_bus.subscribe();
+
+    // exits on SIGTERM
+    let config = ProcessConfig { stop_timeout: Duration::from_secs(3), ..shell("graceful", "sleep 10") };
+    let service_id = app.service_manager.add_service_instance(ProcessService::new(config, &app.service_manager)).await;
+    app.service_manager.start_service(service_id).await.unwrap();
+    next_process_event(&mut events).await;
+
+    let stop_started_at = Instant::now();
+    app.service_manager.stop_service(service_id).await.unwrap();
+    assert!(stop_started_at.elapsed() < Duration::from_secs(3));
+    assert_eq!(HostEvent::ProcessExited { name: "graceful".to_string(), exit_code: None, restart: false }, next_process_event(&mut events).await);
+
+    // ignores SIGTERM, killed after stop timeout
+    let config = ProcessConfig { stop_timeout: Duration::from_millis(200), ..shell("stubborn", "trap '' TERM; while true; do sleep 0.05; done") };
+    let service_id = app.service_manager.add_service_instance(ProcessService::new(config, &app.service_manager)).await;
+    app.service_manager.start_service(service_id).await.unwrap();
+    next_process_event(&mut events).await;
+    tokio::time::sleep(Duration::from_millis(100)).await;
+
+    let stop_started_at = Instant::now();
+    app.service_manager.stop_service(service_id).await.unwrap();
+    assert!(stop_started_at.elapsed() >= Duration::from_millis(200));
+    assert_eq!(HostEvent::ProcessExited { name: "stubborn".to_string(), exit_code: None, restart: false }, next_process_event(&mut events).await);
+}
+
+#[tokio::test]
+async fn process_service_exit() {
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    let mut events = app.event_bus.subscribe();
+
+    let service_id = app.service_manager.add_service_instance(ProcessService::new(shell("finishing", "exit 0"), &app.service_manager)).await;
+    app.service_manager.start_service(service_id).await.unwrap();
+
+    // clean exit without restart stops service
+    let stopped_type_info = tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let HostEvent::ServiceStopped { service_type_info, .. } = events.recv().await.unwrap() {
+                return service_type_info;
+            }
+        }
+    }).await.unwrap();
+
+    assert!(stopped_type_info.type_name.ends_with("ProcessService[finishing]"));
+    assert_eq!(ServiceState::Stopped, app.service_manager.get_state(service_id).await.unwrap());
+}
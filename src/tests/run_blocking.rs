@@ -0,0 +1,109 @@
+use std::sync::{Arc, Mutex};
+
+use anthill_di::DependencyContext;
+use tokio::sync::RwLock;
+
+use crate::{
+    configs::{CoreConfig, CoreConfigSnapshot, ConfigurationSource, RuntimeConfig, RuntimeFlavor},
+    life_time::ILifeTimeManager,
+    services::{IBaseService, IBackgroundService},
+    types::StartupResult,
+    Application,
+    IStartup,
+};
+
+use anthill_di_derive::constructor;
+
+static RUNTIME_THREAD_NAME: Mutex<Option<String>> = Mutex::new(None);
+
+#[derive(constructor)]
+struct TestBackgroundService {
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestBackgroundService {
+    async fn execute(&self) {
+        *RUNTIME_THREAD_NAME.lock().unwrap() = std::thread::current().name().map(str::to_string);
+        self.application_life_time.stop().await;
+    }
+}
+
+#[derive(constructor)]
+struct TestStartup {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IStartup for TestStartup {
+    async fn configure_dependency(&mut self, _root_ioc_context: &mut DependencyContext) -> StartupResult {
+        Ok(())
+    }
+
+    async fn configure_application(&mut self, _core_config: Arc<RwLock<CoreConfigSnapshot>>, app: &mut Application) -> StartupResult {
+        use crate::{life_time::InnerStateLifeTimeManager, services::BackgroundService};
+
+        app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+        app.register_service::<BackgroundService<TestBackgroundService>>().await.unwrap();
+
+        Ok(())
+    }
+}
+
+#[test]
+fn run_blocking() {
+    let core_config = CoreConfig {
+        runtime: RuntimeConfig { flavor: RuntimeFlavor::MultiThread, worker_threads: Some(2), thread_name: Some("test-runtime".to_string()), thread_stack_size: None },
+        ..Default::default()
+    };
+
+    Application::run_blocking::<TestStartup>(ConfigurationSource::in_memory(&core_config)).unwrap();
+
+    assert_eq!(Some("test-runtime".to_string()), *RUNTIME_THREAD_NAME.lock().unwrap());
+}
+
+#[derive(Default)]
+struct TestReport {
+    thread_names: Mutex<Vec<Option<String>>>,
+}
+
+#[derive(constructor)]
+struct TestIsolatedService {
+    report: Arc<TestReport>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestIsolatedService {
+    async fn on_start(&mut self) {
+        self.report.thread_names.lock().unwrap().push(std::thread::current().name().map(str::to_string));
+
+        let report = self.report.clone();
+        tokio::spawn(async move {
+            report.thread_names.lock().unwrap().push(std::thread::current().name().map(str::to_string));
+        }).await.unwrap();
+    }
+
+    async fn on_stop(&mut self) {
+        self.report.thread_names.lock().unwrap().push(std::thread::current().name().map(str::to_string));
+    }
+}
+
+#[tokio::test]
+async fn isolated_service() {
+    use crate::services::IsolatedService;
+
+    let core_config = CoreConfig {
+        isolated_runtime: RuntimeConfig { flavor: RuntimeFlavor::CurrentThread, ..Default::default() },
+        ..Default::default()
+    };
+
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&core_config)).await.unwrap();
+
+    app.root_ioc_context.register_instance(TestReport::default()).await.unwrap();
+    let report = app.root_ioc_context.resolve::<Arc<TestReport>>().await.unwrap();
+
+    let service_id = app.service_manager.add_service::<IsolatedService<TestIsolatedService>>().await.unwrap();
+    app.service_manager.start_service(service_id).await.unwrap();
+    app.service_manager.stop_service(service_id).await.unwrap();
+
+    let isolated_thread_name = Some("TestIsolatedService".to_string());
+    assert_eq!(vec![isolated_thread_name.clone(), isolated_thread_name.clone(), isolated_thread_name], *report.thread_names.lock().unwrap());
+}
@@ -0,0 +1,56 @@
+use std::time::Duration;
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::services::IBackgroundService;
+
+use anthill_di_derive::constructor;
+
+#[derive(constructor)]
+struct TestImportService {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestImportService {
+    async fn execute(&self) {
+        tokio::time::sleep(Duration::from_millis(50)).await;
+    }
+}
+
+#[derive(constructor)]
+struct TestExportService {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestExportService {
+    async fn execute(&self) {
+        tokio::time::sleep(Duration::from_millis(100)).await;
+    }
+
+    fn get_exit_code(&self) -> i32 {
+        3
+    }
+}
+
+#[tokio::test]
+async fn run_to_completion() {
+    use crate::{
+        Application,
+        life_time::InnerStateLifeTimeManager,
+        services::{BackgroundService, CompletionMode, ServiceCompletion, ExecutionStatus},
+    };
+    use anthill_di::types::TypeInfo;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    // never stopped by services
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<BackgroundService<TestImportService>>().await.unwrap();
+    app.register_service::<BackgroundService<TestExportService>>().await.unwrap();
+
+    let report = app.run_to_completion(CompletionMode::AllBackgroundServices).await.unwrap();
+
+    assert_eq!(vec![
+        ServiceCompletion { service_type_info: TypeInfo::from_type::<TestImportService>(), status: ExecutionStatus::Completed { exit_code: 0 } },
+        ServiceCompletion { service_type_info: TypeInfo::from_type::<TestExportService>(), status: ExecutionStatus::Completed { exit_code: 3 } },
+    ], report.completions);
+
+    assert_eq!(3, report.get_exit_code());
+}
@@ -0,0 +1,100 @@
+use std::sync::{Arc, Weak, atomic::{AtomicUsize, Ordering}};
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::services::{IBackgroundService, ServiceScopeFactory};
+
+use anthill_di::{types::BuildDependencyResult, Constructor, DependencyContext};
+use anthill_di_derive::constructor;
+
+#[derive(Default)]
+struct TestCounters {
+    created: AtomicUsize,
+    dropped: AtomicUsize,
+}
+
+struct TestTransaction {
+    counters: Arc<TestCounters>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for TestTransaction {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        let counters: Arc<TestCounters> = ctx.resolve().await?;
+        counters.created.fetch_add(1, Ordering::SeqCst);
+
+        Ok(Self { counters })
+    }
+}
+
+impl Drop for TestTransaction {
+    fn drop(&mut self) {
+        self.counters.dropped.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(constructor)]
+struct TestScopedService {
+    transaction: Weak<TestTransaction>,
+    same_transaction: Weak<TestTransaction>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestScopedService {
+    async fn execute(&self) {
+        assert!(self.transaction.upgrade().is_some());
+        assert!(Weak::ptr_eq(&self.transaction, &self.same_transaction));
+    }
+}
+
+#[derive(constructor)]
+struct TestWorkerService {
+    scope_factory: Arc<ServiceScopeFactory>,
+    counters: Arc<TestCounters>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestWorkerService {
+    async fn execute(&self) {
+        for _message in 0..3 {
+            let scope = self.scope_factory.create_scope().await.unwrap();
+            let dropped_before = self.counters.dropped.load(Ordering::SeqCst);
+
+            let transaction = scope.resolve::<Weak<TestTransaction>>().await.unwrap();
+            let same_transaction = scope.resolve::<Weak<TestTransaction>>().await.unwrap();
+            assert!(Weak::ptr_eq(&transaction, &same_transaction));
+
+            drop(scope);
+            assert!(transaction.upgrade().is_none());
+            assert_eq!(dropped_before + 1, self.counters.dropped.load(Ordering::SeqCst));
+        }
+    }
+}
+
+#[tokio::test]
+async fn scoped_background_service() {
+    use crate::{
+        Application,
+        life_time::InnerStateLifeTimeManager,
+        services::{BackgroundService, ScopedBackgroundService, CompletionMode, ExecutionStatus},
+    };
+    use anthill_di::DependencyLifeCycle;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.root_ioc_context.register_instance(TestCounters::default()).await.unwrap();
+    let counters = app.root_ioc_context.resolve::<Arc<TestCounters>>().await.unwrap();
+    app.root_ioc_context.register_type::<TestTransaction>(DependencyLifeCycle::Scoped).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<ScopedBackgroundService<TestScopedService>>().await.unwrap();
+    app.register_service::<BackgroundService<TestWorkerService>>().await.unwrap();
+
+    let report = app.run_to_completion(CompletionMode::AllBackgroundServices).await.unwrap();
+
+    assert!(report.completions.iter().all(|completion| completion.status == ExecutionStatus::Completed { exit_code: 0 }));
+    assert_eq!(2, report.completions.len());
+
+    // one transaction for scoped service execution and one per worker message, all dropped with their scopes
+    assert_eq!(4, counters.created.load(Ordering::SeqCst));
+    assert_eq!(4, counters.dropped.load(Ordering::SeqCst));
+}
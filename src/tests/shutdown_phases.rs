@@ -0,0 +1,111 @@
+use std::{
+    sync::{Arc, Mutex, atomic::{AtomicBool, Ordering}},
+    time::Duration,
+};
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::{
+    services::{IBaseService, IBackgroundService},
+    life_time::{ILifeTimeManager, ShutdownSignal, ShutdownPhase},
+};
+
+use anthill_di_derive::constructor;
+
+#[derive(Default)]
+struct TestPhaseLog {
+    records: Mutex<Vec<(String, ShutdownPhase)>>,
+}
+
+#[derive(constructor)]
+struct TestDrainingService {
+    log: Arc<TestPhaseLog>,
+    shutdown_signal: Arc<ShutdownSignal>,
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestDrainingService {
+    async fn on_start(&mut self) {
+        self.application_life_time.stop().await;
+    }
+
+    async fn on_drain(&mut self) {
+        self.log.records.lock().unwrap().push(("on_drain".to_string(), self.shutdown_signal.get_phase()));
+    }
+
+    async fn on_stop(&mut self) {
+        self.log.records.lock().unwrap().push(("on_stop".to_string(), self.shutdown_signal.get_phase()));
+    }
+}
+
+#[tokio::test]
+async fn shutdown_phases() {
+    use crate::{Application, life_time::InnerStateLifeTimeManager};
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.root_ioc_context.register_instance(TestPhaseLog::default()).await.unwrap();
+
+    app.register_service::<TestDrainingService>().await.unwrap();
+
+    app.run().await.unwrap();
+
+    let log = app.root_ioc_context.resolve::<Arc<TestPhaseLog>>().await.unwrap();
+    assert_eq!(vec![
+        ("on_drain".to_string(), ShutdownPhase::Draining),
+        ("on_stop".to_string(), ShutdownPhase::Stopping),
+    ], *log.records.lock().unwrap());
+
+    let shutdown_signal = app.root_ioc_context.resolve::<Arc<ShutdownSignal>>().await.unwrap();
+    assert_eq!(ShutdownPhase::Stopped, shutdown_signal.get_phase());
+}
+
+struct TestDropGuard(Arc<AtomicBool>);
+
+impl Drop for TestDropGuard {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+}
+
+#[derive(constructor)]
+struct TestEndlessService {
+    #[custom_resolve(value = "Arc::new(AtomicBool::new(false))")] is_dropped: Arc<AtomicBool>,
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestEndlessService {
+    async fn execute(&self) {
+        let _guard = TestDropGuard(self.is_dropped.clone());
+        self.application_life_time.stop().await;
+        std::future::pending::<()>().await;
+    }
+}
+
+#[tokio::test]
+async fn shutdown_abort_after_stop_timeout() {
+    use crate::{Application, life_time::InnerStateLifeTimeManager, services::BackgroundService, types::AppRunError};
+    use anthill_di::types::TypeInfo;
+    use tokio::sync::RwLock;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    app.core_config.write().await.value.on_stop_timeout = Duration::from_millis(100);
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<BackgroundService<TestEndlessService>>().await.unwrap();
+
+    let result = app.run().await;
+
+    assert_eq!(result.err(), Some(AppRunError::ServiceStopTimeoutExpired {
+        timeout_duration: Duration::from_millis(100),
+        service_type_info: TypeInfo::from_type::<BackgroundService<TestEndlessService>>(),
+    }));
+
+    // aborted task dropped by runtime
+    tokio::task::yield_now().await;
+
+    let service = app.root_ioc_context.resolve::<Arc<RwLock<TestEndlessService>>>().await.unwrap();
+    assert!(service.read().await.is_dropped.load(Ordering::SeqCst));
+}
@@ -0,0 +1,158 @@
+use std::{
+    net::{TcpListener, TcpStream},
+    os::unix::{io::AsRawFd, net::UnixListener, process::CommandExt},
+    process::Command,
+    sync::Arc,
+};
+
+use crate::{
+    configs::{CoreConfig, ConfigurationSource},
+    types::SocketActivationError,
+    ActivatedSockets,
+    ActivatedUnixListener,
+    IActivatedSocketName,
+    Application,
+};
+
+const CHILD_ENV: &str = "ANTHILL_SOCKET_ACTIVATION_CHILD";
+
+struct TestAdminSocket;
+
+impl IActivatedSocketName for TestAdminSocket {
+    const NAME: &'static str = "admin";
+}
+
+/// Run ```socket_activation_child``` in new process with listeners passed as descriptors 3 and 4
+fn run_child(tcp_listener: &TcpListener, unix_listener: &UnixListener, envs: &[(&str, String)]) {
+    // SAFETY: duplicates are closed on exec of any other child, moved to 3 and 4 only for this child
+    let (tcp_fd, unix_fd) = unsafe {
+        (libc::fcntl(tcp_listener.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 10), libc::fcntl(unix_listener.as_raw_fd(), libc::F_DUPFD_CLOEXEC, 10))
+    };
+
+    // shell sets LISTEN_PID to own pid, kept by exec
+    let mut command = Command::new("sh");
+    command.args(["-c", "if [ -n \"$SET_LISTEN_PID\" ]; then export LISTEN_PID=$$; fi; exec \"$0\" \"$@\""])
+        .arg(std::env::current_exe().unwrap())
+        .args(["--exact", "tests::socket_activation::socket_activation_child", "--nocapture"])
+        .env(CHILD_ENV, "1")
+        .envs(envs.iter().map(|(key, value)| (key, value)));
+
+    // SAFETY: only async signal safe calls after fork
+    unsafe {
+        command.pre_exec(move || {
+            if libc::dup2(tcp_fd, 3) < 0 || libc::dup2(unix_fd, 4) < 0 {
+                return Err(std::io::Error::last_os_error());
+            }
+
+            // descriptor after passed ones may be inherited from test runner
+            libc::close(5);
+
+            Ok(())
+        });
+    }
+
+    let output = command.output().unwrap();
+
+    // SAFETY: duplicates owned by test
+    unsafe {
+        libc::close(tcp_fd);
+        libc::close(unix_fd);
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(output.status.success() && stdout.contains("1 passed"), "{stdout}\n{stderr}", stderr = String::from_utf8_lossy(&output.stderr));
+}
+
+#[test]
+fn socket_activation() {
+    let tcp_listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let unix_path = std::env::temp_dir().join(format!("anthill-service-system-activation-{pid}.sock", pid = std::process::id()));
+    _ = std::fs::remove_file(&unix_path);
+    let unix_listener = UnixListener::bind(&unix_path).unwrap();
+
+    let tcp_addr = tcp_listener.local_addr().unwrap().to_string();
+
+    run_child(&tcp_listener, &unix_listener, &[
+        ("SET_LISTEN_PID", "1".to_string()),
+        ("LISTEN_FDS", "2".to_string()),
+        ("LISTEN_FDNAMES", "http:admin".to_string()),
+        ("EXPECTED_NAMES", "http:admin".to_string()),
+        ("EXPECTED_TCP_ADDR", tcp_addr.clone()),
+    ]);
+
+    // LISTEN_PID required
+    run_child(&tcp_listener, &unix_listener, &[
+        ("LISTEN_FDS", "2".to_string()),
+        ("LISTEN_FDNAMES", "http:admin".to_string()),
+        ("EXPECTED_NAMES", "".to_string()),
+    ]);
+
+    // descriptor 5 not passed
+    run_child(&tcp_listener, &unix_listener, &[
+        ("SET_LISTEN_PID", "1".to_string()),
+        ("LISTEN_FDS", "3".to_string()),
+        ("LISTEN_FDNAMES", "http:admin:closed".to_string()),
+        ("EXPECTED_NAMES", "http:admin".to_string()),
+        ("EXPECTED_TCP_ADDR", tcp_addr.clone()),
+    ]);
+
+    // passed to other process
+    run_child(&tcp_listener, &unix_listener, &[
+        ("LISTEN_PID", "1".to_string()),
+        ("LISTEN_FDS", "2".to_string()),
+        ("LISTEN_FDNAMES", "http:admin".to_string()),
+        ("EXPECTED_NAMES", "".to_string()),
+    ]);
+
+    std::fs::remove_file(&unix_path).unwrap();
+}
+
+#[test]
+fn socket_activation_child() {
+    if std::env::var(CHILD_ENV).is_err() {
+        return;
+    }
+
+    // read before runtime start
+    let sockets = ActivatedSockets::from_env();
+
+    // descriptors taken once per process
+    assert!(ActivatedSockets::from_env().get_names().is_empty());
+
+    tokio::runtime::Builder::new_current_thread().enable_all().build().unwrap().block_on(check_activated_sockets(sockets));
+}
+
+async fn check_activated_sockets(sockets: ActivatedSockets) {
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    app.set_activated_sockets(sockets).await.unwrap();
+    app.register_activated_socket::<TestAdminSocket>().await.unwrap();
+
+    let activated_sockets = app.root_ioc_context.resolve::<Arc<ActivatedSockets>>().await.unwrap();
+
+    let expected_names = std::env::var("EXPECTED_NAMES").unwrap();
+    assert_eq!(expected_names.split(':').filter(|name| !name.is_empty()).collect::<Vec<_>>(), activated_sockets.get_names());
+
+    if expected_names.is_empty() {
+        return;
+    }
+
+    assert_eq!(Err(SocketActivationError::UnexpectedSocketType { name: "http".to_string(), expected: "unix".to_string() }), activated_sockets.take_unix_listener("http").map(|_| ()));
+
+    let tcp_listener = activated_sockets.take_tcp_listener("http").unwrap();
+    assert_eq!(std::env::var("EXPECTED_TCP_ADDR").unwrap(), tcp_listener.local_addr().unwrap().to_string());
+
+    tcp_listener.set_nonblocking(true).unwrap();
+    let tcp_listener = tokio::net::TcpListener::from_std(tcp_listener).unwrap();
+    let client = TcpStream::connect(tcp_listener.local_addr().unwrap()).unwrap();
+    let (_, client_addr) = tcp_listener.accept().await.unwrap();
+    assert_eq!(client.local_addr().unwrap(), client_addr);
+
+    let unix_listener = app.root_ioc_context.resolve::<ActivatedUnixListener<TestAdminSocket>>().await.unwrap().into_inner();
+    assert!(unix_listener.local_addr().unwrap().as_pathname().is_some());
+
+    // taken on first resolve
+    assert!(app.root_ioc_context.resolve::<ActivatedUnixListener<TestAdminSocket>>().await.is_err());
+
+    assert_eq!(Err(SocketActivationError::SocketNotFound { name: "http".to_string() }), activated_sockets.take_fd("http").map(|_| ()));
+    assert!(activated_sockets.get_names().is_empty());
+}
@@ -0,0 +1,353 @@
+use std::{
+    sync::{Arc, Mutex, atomic::{AtomicBool, AtomicUsize, Ordering}},
+    time::Duration,
+};
+
+use crate::{
+    configs::{CoreConfig, ConfigurationSource},
+    events::{HostEvent, StopReason},
+    life_time::{ILifeTimeManager, InnerStateLifeTimeManager},
+    metrics::{MetricsRegistry, SERVICE_STALLS},
+    services::{BackgroundService, Heartbeat, HeartbeatRegistry, IBackgroundService, IBaseService, ServiceId, ServiceManager, StallAction},
+    Application,
+};
+
+use anthill_di::{types::{BuildDependencyResult, TypeInfo}, Constructor, DependencyContext};
+use tokio::task::JoinHandle;
+
+#[derive(Default)]
+struct TestExecutions {
+    count: AtomicUsize,
+    is_finished: AtomicBool,
+}
+
+struct TestStalledService {
+    heartbeat: Heartbeat,
+    executions: Arc<TestExecutions>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for TestStalledService {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        let registry: Arc<HeartbeatRegistry> = ctx.resolve().await?;
+
+        Ok(Self {
+            heartbeat: registry.register(Duration::from_millis(100), StallAction::Restart),
+            executions: ctx.resolve().await?,
+        })
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestStalledService {
+    fn on_added(&self, service_id: ServiceId) {
+        self.heartbeat.bind(service_id);
+    }
+
+    async fn execute(&self) {
+        self.heartbeat.beat();
+
+        // first execution hangs
+        if self.executions.count.fetch_add(1, Ordering::SeqCst) == 0 {
+            std::future::pending::<()>().await;
+        }
+
+        while !self.executions.is_finished.load(Ordering::SeqCst) {
+            self.heartbeat.beat();
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    }
+}
+
+struct TestSilentService {
+    heartbeat: Heartbeat,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for TestSilentService {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        let registry: Arc<HeartbeatRegistry> = ctx.resolve().await?;
+        Ok(Self { heartbeat: registry.register(Duration::from_millis(50), StallAction::StopApplication) })
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestSilentService {
+    async fn on_start(&mut self) {}
+
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.heartbeat.bind(service_id);
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+#[derive(Default)]
+struct TestInstances {
+    starts: Mutex<Vec<ServiceId>>,
+    stalled: Mutex<Option<ServiceId>>,
+}
+
+struct TestInstanceService {
+    heartbeat: Heartbeat,
+    instances: Arc<TestInstances>,
+    service_id: Option<ServiceId>,
+    work_task: Option<JoinHandle<()>>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for TestInstanceService {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        let registry: Arc<HeartbeatRegistry> = ctx.resolve().await?;
+
+        Ok(Self {
+            heartbeat: registry.register(Duration::from_millis(100), StallAction::Restart),
+            instances: ctx.resolve().await?,
+            service_id: None,
+            work_task: None,
+        })
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestInstanceService {
+    async fn on_start(&mut self) {
+        let service_id = self.service_id.unwrap();
+        self.instances.starts.lock().unwrap().push(service_id);
+
+        let heartbeat = self.heartbeat.clone();
+        let instances = self.instances.clone();
+        self.work_task = Some(tokio::spawn(async move {
+            loop {
+                if *instances.stalled.lock().unwrap() != Some(service_id) {
+                    heartbeat.beat();
+                }
+
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }));
+    }
+
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.service_id = Some(service_id);
+        self.heartbeat.bind(service_id);
+    }
+
+    async fn on_stop(&mut self) {
+        if let Some(work_task) = self.work_task.take() {
+            work_task.abort();
+        }
+    }
+}
+
+#[tokio::test]
+async fn stall_watchdog_restart() {
+    let core_config = CoreConfig { on_stop_timeout: Duration::from_millis(100), ..Default::default() };
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&core_config)).await.unwrap();
+
+    app.root_ioc_context.register_instance(TestExecutions::default()).await.unwrap();
+    let executions = app.root_ioc_context.resolve::<Arc<TestExecutions>>().await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<BackgroundService<TestStalledService>>().await.unwrap();
+    app.register_stall_watchdog(Duration::from_millis(20)).await;
+
+    let life_time_manager = app.root_ioc_context.resolve::<Arc<dyn ILifeTimeManager>>().await.unwrap();
+    let registry = app.root_ioc_context.resolve::<Arc<HeartbeatRegistry>>().await.unwrap();
+    let mut events = app.event_bus.subscribe();
+
+    let run_task = tokio::spawn(async move { app.run().await });
+
+    let service_type_info = TypeInfo::from_type::<BackgroundService<TestStalledService>>();
+
+    let mut health = Vec::new();
+    while health.len() < 2 {
+        match events.recv().await.unwrap() {
+            HostEvent::ServiceUnhealthy { service_type_info: event_type_info, stalled_for, .. } => {
+                assert_eq!(service_type_info, event_type_info);
+                assert!(stalled_for > Duration::from_millis(100));
+                health.push(false);
+            },
+            HostEvent::ServiceHealthy { service_type_info: event_type_info, .. } => {
+                assert_eq!(service_type_info, event_type_info);
+                health.push(true);
+            },
+            _ => {},
+        }
+    }
+
+    assert_eq!(vec![false, true], health);
+    assert_eq!(2, executions.count.load(Ordering::SeqCst));
+
+    // restarted execution keeps heartbeat
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(registry.get_unhealthy().is_empty());
+    assert_eq!(2, executions.count.load(Ordering::SeqCst));
+
+    executions.is_finished.store(true, Ordering::SeqCst);
+    life_time_manager.stop().await;
+
+    run_task.await.unwrap().unwrap();
+}
+
+#[tokio::test]
+async fn stall_watchdog_stop_application() {
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<TestSilentService>().await.unwrap();
+    app.register_stall_watchdog(Duration::from_millis(10)).await;
+
+    let mut events = app.event_bus.subscribe();
+
+    app.run().await.unwrap();
+
+    let service_type_info = TypeInfo::from_type::<TestSilentService>();
+    let mut is_unhealthy_reported = false;
+    let mut stop_reason = None;
+    while let Ok(event) = events.try_recv() {
+        match event {
+            HostEvent::ServiceUnhealthy { service_type_info: event_type_info, .. } => {
+                assert_eq!(service_type_info, event_type_info);
+                is_unhealthy_reported = true;
+            },
+            HostEvent::StopRequested { reason } => stop_reason = Some(reason),
+            _ => {},
+        }
+    }
+
+    assert!(is_unhealthy_reported);
+    assert_eq!(Some(StopReason::ServiceStalled { service_type_info: service_type_info.clone() }), stop_reason);
+
+    let registry = app.root_ioc_context.resolve::<Arc<MetricsRegistry>>().await.unwrap();
+    assert_eq!(Some(1), registry.get_counter(SERVICE_STALLS, &[("service", service_type_info.type_name.as_str())]));
+}
+
+#[tokio::test]
+async fn stall_watchdog_restart_instance() {
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.root_ioc_context.register_instance(TestInstances::default()).await.unwrap();
+    let instances = app.root_ioc_context.resolve::<Arc<TestInstances>>().await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+
+    let service_manager = app.root_ioc_context.resolve::<Arc<ServiceManager>>().await.unwrap();
+    let stalled_service_id = service_manager.add_service::<TestInstanceService>().await.unwrap();
+    let sibling_service_id = service_manager.add_service::<TestInstanceService>().await.unwrap();
+    let watchdog_service_id = app.register_stall_watchdog(Duration::from_millis(20)).await;
+
+    let mut events = app.event_bus.subscribe();
+
+    service_manager.start_service(stalled_service_id).await.unwrap();
+    service_manager.start_service(sibling_service_id).await.unwrap();
+    service_manager.start_service(watchdog_service_id).await.unwrap();
+
+    *instances.stalled.lock().unwrap() = Some(stalled_service_id);
+
+    let mut health = Vec::new();
+    while health.len() < 2 {
+        match events.recv().await.unwrap() {
+            HostEvent::ServiceUnhealthy { service_id, .. } => health.push((service_id, false)),
+            HostEvent::ServiceHealthy { service_id, .. } => {
+                *instances.stalled.lock().unwrap() = None;
+                health.push((service_id, true));
+            },
+            _ => {},
+        }
+    }
+
+    assert_eq!(vec![(stalled_service_id, false), (stalled_service_id, true)], health);
+    assert_eq!(vec![stalled_service_id, sibling_service_id, stalled_service_id], *instances.starts.lock().unwrap());
+
+    service_manager.stop_all().await.unwrap();
+}
+
+struct TestHangingService {
+    heartbeat: Heartbeat,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for TestHangingService {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        let registry: Arc<HeartbeatRegistry> = ctx.resolve().await?;
+        Ok(Self { heartbeat: registry.register(Duration::from_millis(50), StallAction::Restart) })
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestHangingService {
+    async fn on_start(&mut self) {}
+
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.heartbeat.bind(service_id);
+    }
+
+    /// Restart waits stop timeout
+    async fn on_stop(&mut self) {
+        std::future::pending::<()>().await;
+    }
+}
+
+struct TestLateStallService {
+    heartbeat: Heartbeat,
+    work_task: Option<JoinHandle<()>>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl Constructor for TestLateStallService {
+    async fn ctor(ctx: DependencyContext) -> BuildDependencyResult<Self> {
+        let registry: Arc<HeartbeatRegistry> = ctx.resolve().await?;
+        Ok(Self { heartbeat: registry.register(Duration::from_millis(50), StallAction::Report), work_task: None })
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestLateStallService {
+    async fn on_start(&mut self) {
+        let heartbeat = self.heartbeat.clone();
+        self.work_task = Some(tokio::spawn(async move {
+            for _ in 0..15 {
+                heartbeat.beat();
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        }));
+    }
+
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.heartbeat.bind(service_id);
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+#[tokio::test]
+async fn stall_watchdog_restart_not_blocking() {
+    let core_config = CoreConfig { on_stop_timeout: Duration::from_millis(500), ..Default::default() };
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&core_config)).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+
+    let service_manager = app.root_ioc_context.resolve::<Arc<ServiceManager>>().await.unwrap();
+    let hanging_service_id = service_manager.add_service::<TestHangingService>().await.unwrap();
+    let late_stall_service_id = service_manager.add_service::<TestLateStallService>().await.unwrap();
+    let watchdog_service_id = app.register_stall_watchdog(Duration::from_millis(10)).await;
+
+    let mut events = app.event_bus.subscribe();
+
+    service_manager.start_service(hanging_service_id).await.unwrap();
+    service_manager.start_service(late_stall_service_id).await.unwrap();
+    service_manager.start_service(watchdog_service_id).await.unwrap();
+
+    // late stall reported while restart of hanging service waits stop timeout
+    let mut received = Vec::new();
+    while received.len() < 3 {
+        match events.recv().await.unwrap() {
+            HostEvent::ServiceUnhealthy { service_id, .. } => received.push((service_id, "unhealthy")),
+            HostEvent::ServiceAborted { service_id, .. } => received.push((service_id, "aborted")),
+            _ => {},
+        }
+    }
+
+    assert_eq!(vec![(hanging_service_id, "unhealthy"), (late_stall_service_id, "unhealthy"), (hanging_service_id, "aborted")], received);
+}
@@ -0,0 +1,149 @@
+use crate::{configs::CoreConfigSnapshot, Application, types::{StartupResult, StartupError}};
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::{IStartup, life_time::InnerStateLifeTimeManager};
+use std::sync::{Arc, Mutex};
+
+use anthill_di::{
+    DependencyContext,
+    types::TypeInfo,
+};
+use tokio::sync::RwLock;
+
+use anthill_di_derive::constructor;
+
+#[derive(Default)]
+struct TestStartupLog {
+    records: Mutex<Vec<String>>,
+}
+
+impl TestStartupLog {
+    fn push(&self, record: &str) {
+        self.records.lock().unwrap().push(record.to_string());
+    }
+}
+
+#[derive(constructor)]
+struct TestDependentStartup {
+    log: Arc<TestStartupLog>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IStartup for TestDependentStartup {
+    async fn configure_dependency(&mut self, _root_ioc_context: &mut DependencyContext) -> StartupResult {
+        self.log.push("dependent:dependency");
+        Ok(())
+    }
+
+    async fn configure_application(&mut self, _core_config: Arc<RwLock<CoreConfigSnapshot>>, _app: &mut Application) -> StartupResult {
+        self.log.push("dependent:application");
+        Ok(())
+    }
+
+    fn get_priority(&self) -> i32 {
+        -10
+    }
+
+    fn get_dependencies(&self) -> Vec<TypeInfo> {
+        vec![TypeInfo::from_type::<TestBaseStartup>()]
+    }
+}
+
+#[derive(constructor)]
+struct TestBaseStartup {
+    log: Arc<TestStartupLog>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IStartup for TestBaseStartup {
+    async fn configure_dependency(&mut self, _root_ioc_context: &mut DependencyContext) -> StartupResult {
+        self.log.push("base:dependency");
+        Ok(())
+    }
+
+    async fn configure_application(&mut self, _core_config: Arc<RwLock<CoreConfigSnapshot>>, app: &mut Application) -> StartupResult {
+        self.log.push("base:application");
+
+        app.register_life_time_manager::<InnerStateLifeTimeManager>().await
+            .map_err(StartupError::AddLifeTimeManagerError)?;
+
+        app.root_ioc_context.resolve::<Arc<dyn crate::life_time::ILifeTimeManager>>().await
+            .map_err(StartupError::IocBuildDependencyError)?
+            .stop().await;
+
+        Ok(())
+    }
+
+    fn get_priority(&self) -> i32 {
+        10
+    }
+}
+
+#[derive(constructor)]
+struct TestPriorityStartup {
+    log: Arc<TestStartupLog>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IStartup for TestPriorityStartup {
+    async fn configure_dependency(&mut self, _root_ioc_context: &mut DependencyContext) -> StartupResult {
+        self.log.push("priority:dependency");
+        Ok(())
+    }
+
+    async fn configure_application(&mut self, _core_config: Arc<RwLock<CoreConfigSnapshot>>, _app: &mut Application) -> StartupResult {
+        self.log.push("priority:application");
+        Ok(())
+    }
+}
+
+#[derive(constructor)]
+struct TestFailingStartup {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IStartup for TestFailingStartup {
+    async fn configure_dependency(&mut self, _root_ioc_context: &mut DependencyContext) -> StartupResult {
+        Err(StartupError::Custom { message: "test failure".to_string() })
+    }
+
+    async fn configure_application(&mut self, _core_config: Arc<RwLock<CoreConfigSnapshot>>, _app: &mut Application) -> StartupResult {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn startup_order() {
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    app.root_ioc_context.register_instance(TestStartupLog::default()).await.unwrap();
+
+    app.register_startup::<TestDependentStartup>().await.unwrap();
+    app.register_startup::<TestBaseStartup>().await.unwrap();
+    app.register_startup::<TestPriorityStartup>().await.unwrap();
+
+    app.run().await.unwrap();
+
+    let log = app.root_ioc_context.resolve::<Arc<TestStartupLog>>().await.unwrap();
+    assert_eq!(vec![
+        "priority:dependency",
+        "base:dependency",
+        "dependent:dependency",
+        "priority:application",
+        "base:application",
+        "dependent:application",
+    ], *log.records.lock().unwrap());
+}
+
+#[tokio::test]
+async fn startup_failed() {
+    use crate::types::AppRunError;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_startup::<TestFailingStartup>().await.unwrap();
+
+    let result = app.run().await;
+
+    assert_eq!(result.err(), Some(AppRunError::StartupFailed {
+        startup_type_info: TypeInfo::from_type::<TestFailingStartup>(),
+        err: StartupError::Custom { message: "test failure".to_string() },
+    }));
+}
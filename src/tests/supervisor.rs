@@ -0,0 +1,132 @@
+use std::{
+    any::TypeId,
+    sync::{Arc, atomic::{AtomicUsize, Ordering}},
+    time::Duration,
+};
+
+use crate::configs::{CoreConfig, ConfigurationSource};
+use crate::{services::IBackgroundService, life_time::ILifeTimeManager};
+
+use anthill_di_derive::constructor;
+
+#[derive(Default)]
+struct TestCounters {
+    failing_executions: AtomicUsize,
+    counting_executions: AtomicUsize,
+}
+
+#[derive(constructor)]
+struct TestFailingOnceService {
+    counters: Arc<TestCounters>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestFailingOnceService {
+    async fn execute(&self) {
+        if self.counters.failing_executions.fetch_add(1, Ordering::SeqCst) == 0 {
+            panic!("test failure");
+        }
+    }
+}
+
+#[derive(constructor)]
+struct TestCountingService {
+    counters: Arc<TestCounters>,
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestCountingService {
+    async fn execute(&self) {
+        if self.counters.counting_executions.fetch_add(1, Ordering::SeqCst) == 1 {
+            self.application_life_time.stop().await;
+        }
+    }
+}
+
+#[derive(constructor)]
+struct TestAlwaysFailingService {
+    counters: Arc<TestCounters>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestAlwaysFailingService {
+    async fn execute(&self) {
+        self.counters.failing_executions.fetch_add(1, Ordering::SeqCst);
+        panic!("test failure");
+    }
+}
+
+#[tokio::test]
+async fn supervisor_one_for_all_restart() {
+    use crate::{
+        Application,
+        life_time::InnerStateLifeTimeManager,
+        services::{BackgroundService, SupervisorBuilder, SupervisorStrategy},
+    };
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.root_ioc_context.register_instance(TestCounters::default()).await.unwrap();
+
+    app.register_supervisor(SupervisorBuilder::new("workers")
+        .with_strategy(SupervisorStrategy::OneForAll)
+        .add_service::<BackgroundService<TestFailingOnceService>>()
+        .add_service::<BackgroundService<TestCountingService>>()
+    ).await;
+
+    app.run().await.unwrap();
+
+    let counters = app.root_ioc_context.resolve::<Arc<TestCounters>>().await.unwrap();
+    assert_eq!(2, counters.failing_executions.load(Ordering::SeqCst));
+    assert_eq!(2, counters.counting_executions.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn supervisor_escalation() {
+    use crate::{
+        Application,
+        events::{HostEvent, StopReason},
+        life_time::InnerStateLifeTimeManager,
+        services::{BackgroundService, ServiceFailure, Supervisor, SupervisorBuilder},
+        types::AppRunError,
+    };
+    use anthill_di::types::TypeInfo;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    let mut events = app.event_bus.subscribe();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.root_ioc_context.register_instance(TestCounters::default()).await.unwrap();
+
+    app.register_supervisor(SupervisorBuilder::new("root")
+        .with_intensity(1, Duration::from_secs(60))
+        .add_supervisor(SupervisorBuilder::new("nested")
+            .with_intensity(0, Duration::from_secs(60))
+            .add_service::<BackgroundService<TestAlwaysFailingService>>()
+        )
+    ).await;
+
+    // stopped by root supervisor through life time manager, failure returned from run
+    let Err(AppRunError::ServiceFailed(ServiceFailure { service_type_info, message })) = app.run().await else {
+        panic!("Escalated failure expected");
+    };
+
+    assert_eq!(TypeId::of::<Supervisor>(), service_type_info.type_id);
+    assert!(service_type_info.type_name.ends_with("[root]"), "{service_type_info:?}");
+    assert!(message.contains("[nested]"), "{message}");
+    assert!(message.contains(&TypeInfo::from_type::<TestAlwaysFailingService>().type_name), "{message}");
+
+    let mut stop_reason = None;
+    while let Ok(event) = events.try_recv() {
+        if let HostEvent::StopRequested { reason } = event {
+            stop_reason = Some(reason);
+        }
+    }
+
+    assert_eq!(Some(StopReason::SupervisorEscalated { supervisor_type_info: service_type_info }), stop_reason);
+
+    let counters = app.root_ioc_context.resolve::<Arc<TestCounters>>().await.unwrap();
+    assert_eq!(2, counters.failing_executions.load(Ordering::SeqCst));
+}
@@ -0,0 +1,101 @@
+use std::{os::unix::net::UnixDatagram, sync::Arc, time::Duration};
+
+use crate::{
+    configs::{CoreConfig, ConfigurationSource},
+    life_time::{ILifeTimeManager, InnerStateLifeTimeManager},
+    services::IBaseService,
+    Application,
+    SystemdNotifier,
+};
+
+use anthill_di_derive::constructor;
+
+#[derive(constructor)]
+struct TestNotifyService {
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestNotifyService {
+    async fn on_start(&mut self) {
+        let application_life_time = self.application_life_time.clone();
+
+        tokio::spawn(async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            application_life_time.stop().await;
+        });
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+fn receive_all(socket: &UnixDatagram) -> Vec<String> {
+    socket.set_nonblocking(true).unwrap();
+
+    let mut buffer = [0u8; 4096];
+    let mut messages = Vec::new();
+    while let Ok(len) = socket.recv(&mut buffer) {
+        messages.push(String::from_utf8_lossy(&buffer[..len]).to_string());
+    }
+
+    messages
+}
+
+#[tokio::test]
+async fn systemd_notify() {
+    let path = std::env::temp_dir().join(format!("anthill-service-system-notify-{pid}.sock", pid = std::process::id()));
+    _ = std::fs::remove_file(&path);
+    let systemd = UnixDatagram::bind(&path).unwrap();
+    systemd.set_read_timeout(Some(Duration::from_millis(500))).unwrap();
+
+    // receiver queue is short, read concurrently like systemd does
+    let receiver = std::thread::spawn(move || {
+        let mut buffer = [0u8; 4096];
+        let mut messages = Vec::new();
+        while let Ok(len) = systemd.recv(&mut buffer) {
+            messages.push(String::from_utf8_lossy(&buffer[..len]).to_string());
+        }
+
+        messages
+    });
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<TestNotifyService>().await.unwrap();
+    app.set_systemd_notifier(SystemdNotifier::connect(&path.to_string_lossy()).unwrap().with_watchdog_interval(Duration::from_millis(10)));
+
+    app.run().await.unwrap();
+
+    let messages = receiver.join().unwrap();
+    std::fs::remove_file(&path).unwrap();
+
+    assert!(messages.iter().any(|message| message == "WATCHDOG=1"));
+
+    let messages: Vec<_> = messages.into_iter().filter(|message| message != "WATCHDOG=1").collect();
+    let position = |expected: &str| messages.iter().position(|message| message == expected).unwrap_or_else(|| panic!("[{expected}] not found in {messages:?}"));
+
+    let service_name = std::any::type_name::<TestNotifyService>();
+    let starting = position(&format!("STATUS=Starting [{service_name}]"));
+    let started = position(&format!("STATUS=Started [{service_name}]"));
+    let ready = position("READY=1\nSTATUS=Running");
+    let stopping = position("STOPPING=1\nSTATUS=Stopping");
+    let stopped = position(&format!("STATUS=Stopped [{service_name}]"));
+
+    assert!(starting < started && started < ready && ready < stopping && stopping < stopped);
+    assert_eq!(Some(&"STATUS=Stopped".to_string()), messages.last());
+}
+
+#[cfg(target_os = "linux")]
+#[test]
+fn systemd_notify_abstract_socket() {
+    use std::os::{linux::net::SocketAddrExt, unix::net::SocketAddr};
+
+    let name = format!("anthill-service-system-notify-{pid}", pid = std::process::id());
+    let systemd = UnixDatagram::bind_addr(&SocketAddr::from_abstract_name(&name).unwrap()).unwrap();
+
+    let notifier = SystemdNotifier::connect(&format!("@{name}")).unwrap();
+    notifier.status("Loading\ncache").unwrap();
+    notifier.ready().unwrap();
+
+    assert_eq!(vec!["STATUS=Loading cache".to_string(), "READY=1".to_string()], receive_all(&systemd));
+}
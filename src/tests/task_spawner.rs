@@ -0,0 +1,224 @@
+use std::{
+    sync::{Arc, atomic::{AtomicBool, AtomicUsize, Ordering}},
+    time::Duration,
+};
+
+use crate::{
+    configs::{CoreConfig, ConfigurationSource},
+    life_time::{ILifeTimeManager, InnerStateLifeTimeManager},
+    services::{IBaseService, ServiceId, ServiceManager, ServiceState, TaskSpawner, TaskTracker},
+    types::{ServiceManagerError, TaskSpawnerError},
+    Application,
+};
+
+use anthill_di::types::TypeInfo;
+use anthill_di_derive::constructor;
+
+#[derive(Default)]
+struct TestTaskState {
+    ticks: AtomicUsize,
+    is_cancelled: AtomicBool,
+    is_dropped: AtomicBool,
+}
+
+struct DropGuard(Arc<TestTaskState>);
+
+impl Drop for DropGuard {
+    fn drop(&mut self) {
+        self.0.is_dropped.store(true, Ordering::SeqCst);
+    }
+}
+
+#[derive(constructor)]
+struct TestGracefulService {
+    spawner: TaskSpawner,
+    state: Arc<TestTaskState>,
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestGracefulService {
+    async fn on_start(&mut self) {
+        let spawner = self.spawner.clone();
+        let state = self.state.clone();
+
+        self.spawner.spawn("ticker", async move {
+            loop {
+                tokio::select! {
+                    _ = spawner.cancelled() => break,
+                    _ = tokio::time::sleep(Duration::from_millis(10)) => { state.ticks.fetch_add(1, Ordering::SeqCst); },
+                }
+            }
+
+            // graceful finish awaited by host
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            state.is_cancelled.store(true, Ordering::SeqCst);
+        }).unwrap();
+
+        let application_life_time = self.application_life_time.clone();
+        self.spawner.spawn("stopper", async move {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            application_life_time.stop().await;
+        }).unwrap();
+
+        assert_eq!(vec!["ticker".to_string(), "stopper".to_string()], self.spawner.get_running_tasks());
+    }
+
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.spawner.bind(service_id);
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+#[derive(constructor)]
+struct TestStubbornService {
+    spawner: TaskSpawner,
+    state: Arc<TestTaskState>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestStubbornService {
+    async fn on_start(&mut self) {
+        let guard = DropGuard(self.state.clone());
+
+        self.spawner.spawn("stubborn", async move {
+            let _guard = guard;
+            std::future::pending::<()>().await;
+        }).unwrap();
+    }
+
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.spawner.bind(service_id);
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+#[derive(constructor)]
+struct TestTickingService {
+    spawner: TaskSpawner,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestTickingService {
+    async fn on_start(&mut self) {
+        let spawner = self.spawner.clone();
+
+        self.spawner.spawn("ticker", async move {
+            spawner.cancelled().await;
+        }).unwrap();
+    }
+
+    async fn on_added(&mut self, service_id: ServiceId) {
+        self.spawner.bind(service_id);
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+#[tokio::test]
+async fn task_spawner_graceful_stop() {
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.root_ioc_context.register_instance(TestTaskState::default()).await.unwrap();
+    let state = app.root_ioc_context.resolve::<Arc<TestTaskState>>().await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<TestGracefulService>().await.unwrap();
+
+    app.run().await.unwrap();
+
+    assert!(state.ticks.load(Ordering::SeqCst) > 0);
+    assert!(state.is_cancelled.load(Ordering::SeqCst));
+
+    let task_tracker = app.root_ioc_context.resolve::<Arc<TaskTracker>>().await.unwrap();
+    for service_id in app.service_manager.get_service_ids().await {
+        assert!(task_tracker.get_running_tasks(service_id).is_empty());
+    }
+}
+
+#[tokio::test]
+async fn task_spawner_stop_timeout() {
+    let core_config = CoreConfig { on_stop_timeout: Duration::from_millis(50), ..Default::default() };
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&core_config)).await.unwrap();
+
+    app.root_ioc_context.register_instance(TestTaskState::default()).await.unwrap();
+    let state = app.root_ioc_context.resolve::<Arc<TestTaskState>>().await.unwrap();
+
+    let service_manager = app.root_ioc_context.resolve::<Arc<ServiceManager>>().await.unwrap();
+    let service_id = service_manager.add_service::<TestStubbornService>().await.unwrap();
+
+    service_manager.start_service(service_id).await.unwrap();
+
+    assert_eq!(
+        Err(ServiceManagerError::ServiceStopTimeoutExpired { timeout_duration: Duration::from_millis(50), service_type_info: TypeInfo::from_type::<TestStubbornService>() }),
+        service_manager.stop_service(service_id).await,
+    );
+    assert_eq!(Ok(ServiceState::Aborted), service_manager.get_state(service_id).await);
+
+    // aborted task dropped when runtime polls it
+    for _ in 0..10 {
+        tokio::task::yield_now().await;
+    }
+    assert!(state.is_dropped.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn task_spawner_same_type_instances() {
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    let service_manager = app.root_ioc_context.resolve::<Arc<ServiceManager>>().await.unwrap();
+    let task_tracker = app.root_ioc_context.resolve::<Arc<TaskTracker>>().await.unwrap();
+
+    let first_service_id = service_manager.add_service::<TestTickingService>().await.unwrap();
+    let second_service_id = service_manager.add_service::<TestTickingService>().await.unwrap();
+
+    service_manager.start_service(first_service_id).await.unwrap();
+    service_manager.start_service(second_service_id).await.unwrap();
+
+    service_manager.stop_service(first_service_id).await.unwrap();
+
+    assert!(task_tracker.get_running_tasks(first_service_id).is_empty());
+    assert_eq!(vec!["ticker".to_string()], task_tracker.get_running_tasks(second_service_id));
+
+    service_manager.stop_service(second_service_id).await.unwrap();
+    assert!(task_tracker.get_running_tasks(second_service_id).is_empty());
+}
+
+#[tokio::test]
+async fn task_spawner_not_bound() {
+    let app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    let spawner = app.root_ioc_context.resolve::<TaskSpawner>().await.unwrap();
+    let is_spawned = Arc::new(AtomicBool::new(false));
+
+    let is_spawned_ref = is_spawned.clone();
+    let result = spawner.spawn("leaked", async move {
+        is_spawned_ref.store(true, Ordering::SeqCst);
+    });
+
+    assert_eq!(Some(TaskSpawnerError::NotBound { task: "leaked".to_string() }), result.err());
+
+    tokio::task::yield_now().await;
+    assert!(!is_spawned.load(Ordering::SeqCst));
+}
+
+#[tokio::test]
+async fn task_spawner_supervised_child() {
+    use crate::services::SupervisorBuilder;
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+
+    app.root_ioc_context.register_instance(TestTaskState::default()).await.unwrap();
+    let state = app.root_ioc_context.resolve::<Arc<TestTaskState>>().await.unwrap();
+
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_supervisor(SupervisorBuilder::new("graceful").add_service::<TestGracefulService>()).await;
+
+    app.run().await.unwrap();
+
+    // child spawner bound by supervisor, tasks cancelled and awaited on child stop
+    assert!(state.ticks.load(Ordering::SeqCst) > 0);
+    assert!(state.is_cancelled.load(Ordering::SeqCst));
+}
@@ -0,0 +1,119 @@
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    configs::CoreConfig,
+    services::{IBaseService, ServiceState},
+    life_time::{ShutdownSignal, ShutdownPhase},
+    testing::TestHost,
+};
+
+use anthill_di_derive::constructor;
+
+#[derive(constructor)]
+struct TestHostedService {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestHostedService {
+    async fn on_start(&mut self) {}
+    async fn on_stop(&mut self) {}
+}
+
+#[derive(constructor)]
+struct TestHangingService {
+    shutdown_signal: Arc<ShutdownSignal>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestHangingService {
+    async fn on_start(&mut self) {}
+
+    async fn on_drain(&mut self) {
+        self.shutdown_signal.wait_for_phase(ShutdownPhase::Stopped).await;
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+#[derive(constructor)]
+struct TestPanicService {}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBaseService for TestPanicService {
+    async fn on_start(&mut self) {
+        panic!("test start panic");
+    }
+
+    async fn on_stop(&mut self) {}
+}
+
+#[tokio::test]
+async fn test_host() {
+    let mut host = TestHost::new().await.unwrap();
+
+    host.get_app().register_service::<TestHostedService>().await.unwrap();
+
+    host.start();
+    assert!(host.wait_for_state::<TestHostedService>(ServiceState::Running, Duration::from_secs(1)).await);
+
+    host.stop().await.unwrap();
+
+    assert_eq!(vec![
+        ServiceState::Pending,
+        ServiceState::Starting,
+        ServiceState::Running,
+        ServiceState::Stopping,
+        ServiceState::Stopped,
+    ], host.get_service_states::<TestHostedService>());
+
+    assert_eq!(ShutdownPhase::Stopped, host.get_shutdown_signal().get_phase());
+}
+
+#[tokio::test]
+async fn test_host_abort() {
+    use crate::events::{HostEvent, StopReason};
+
+    let mut host = TestHost::with_core_config(CoreConfig { drain_timeout: Duration::from_secs(60), ..Default::default() }).await.unwrap();
+
+    host.get_app().register_service::<TestHangingService>().await.unwrap();
+
+    host.start();
+    assert!(host.wait_for_state::<TestHangingService>(ServiceState::Running, Duration::from_secs(1)).await);
+
+    // stuck in drain phase until abort
+    host.abort().await.unwrap();
+
+    let stop_reasons: Vec<_> = host.get_host_events().into_iter()
+        .filter_map(|event| if let HostEvent::StopRequested { reason } = event { Some(reason) } else { None })
+        .collect();
+    assert_eq!(vec![StopReason::LifeTimeManager, StopReason::Abort], stop_reasons);
+
+    assert_eq!(Some(&ServiceState::Aborted), host.get_service_states::<TestHangingService>().last());
+    assert_eq!(Duration::from_secs(60), host.get_stored_core_config().drain_timeout);
+}
+
+#[tokio::test]
+async fn test_host_errors() {
+    use crate::{events::HostEvent, testing::TestHostError};
+    use anthill_di::types::TypeInfo;
+
+    let mut host = TestHost::new().await.unwrap();
+
+    host.get_app().register_service::<TestPanicService>().await.unwrap();
+
+    host.start();
+
+    // failed state is transient, service aborted when application start fails
+    assert!(host.wait_for_state::<TestPanicService>(ServiceState::Failed, Duration::from_secs(1)).await);
+    assert!(host.wait_for_exit().await.is_err());
+    assert_eq!(Some(&ServiceState::Aborted), host.get_service_states::<TestPanicService>().last());
+
+    let service_id = host.get_service_manager().get_service_ids().await[0];
+    let service_type_info = TypeInfo::from_type::<TestPanicService>();
+
+    let errors = host.get_errors();
+    assert_eq!(2, errors.len());
+    assert_eq!(TestHostError::ServiceFailed { service_id, service_type_info: service_type_info.clone() }, errors[0]);
+    assert!(matches!(&errors[1], TestHostError::AppRunFailed { message } if message.contains("test start panic")), "{errors:?}");
+
+    assert!(host.get_host_events().contains(&HostEvent::ServiceAborted { service_id, service_type_info }));
+}
@@ -0,0 +1,118 @@
+use std::{
+    collections::BTreeMap,
+    fmt::Debug,
+    sync::{Arc, Mutex, atomic::{AtomicU64, Ordering}},
+};
+
+use tracing::{
+    field::{Field, Visit},
+    span::{Attributes, Id, Record},
+    Event, Metadata, Subscriber,
+};
+
+use crate::{life_time::ILifeTimeManager, services::IBackgroundService};
+
+use anthill_di_derive::constructor;
+
+type RecordedFields = BTreeMap<String, String>;
+
+struct FieldsVisitor<'a>(&'a mut RecordedFields);
+
+impl Visit for FieldsVisitor<'_> {
+    fn record_debug(&mut self, field: &Field, value: &dyn Debug) {
+        self.0.insert(field.name().to_string(), format!("{value:?}").trim_matches('"').to_string());
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.0.insert(field.name().to_string(), value.to_string());
+    }
+}
+
+#[derive(Default, Clone)]
+struct RecordingSubscriber {
+    last_span_id: Arc<AtomicU64>,
+    spans: Arc<Mutex<Vec<RecordedFields>>>,
+    events: Arc<Mutex<Vec<RecordedFields>>>,
+}
+
+impl Subscriber for RecordingSubscriber {
+    fn enabled(&self, _metadata: &Metadata<'_>) -> bool {
+        true
+    }
+
+    fn new_span(&self, span: &Attributes<'_>) -> Id {
+        let mut fields = RecordedFields::new();
+        fields.insert("name".to_string(), span.metadata().name().to_string());
+        span.record(&mut FieldsVisitor(&mut fields));
+        self.spans.lock().unwrap().push(fields);
+
+        Id::from_u64(self.last_span_id.fetch_add(1, Ordering::SeqCst) + 1)
+    }
+
+    fn record(&self, _span: &Id, _values: &Record<'_>) {}
+
+    fn record_follows_from(&self, _span: &Id, _follows: &Id) {}
+
+    fn event(&self, event: &Event<'_>) {
+        let mut fields = RecordedFields::new();
+        event.record(&mut FieldsVisitor(&mut fields));
+        self.events.lock().unwrap().push(fields);
+    }
+
+    fn enter(&self, _span: &Id) {}
+
+    fn exit(&self, _span: &Id) {}
+}
+
+#[derive(constructor)]
+struct TestTracedService {
+    application_life_time: Arc<dyn ILifeTimeManager>,
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IBackgroundService for TestTracedService {
+    async fn execute(&self) {
+        self.application_life_time.stop().await;
+    }
+}
+
+#[tokio::test]
+async fn tracing_spans() {
+    use crate::{
+        configs::{CoreConfig, ConfigurationSource},
+        life_time::InnerStateLifeTimeManager,
+        services::BackgroundService,
+        Application,
+    };
+    use anthill_di::types::TypeInfo;
+
+    let subscriber = RecordingSubscriber::default();
+    let _guard = tracing::subscriber::set_default(subscriber.clone());
+
+    let mut app = Application::with_configuration_source(ConfigurationSource::in_memory(&CoreConfig::default())).await.unwrap();
+    app.register_life_time_manager::<InnerStateLifeTimeManager>().await.unwrap();
+    app.register_service::<BackgroundService<TestTracedService>>().await.unwrap();
+
+    app.run().await.unwrap();
+
+    let background_service_name = TypeInfo::from_type::<BackgroundService<TestTracedService>>().type_name;
+    let service_name = TypeInfo::from_type::<TestTracedService>().type_name;
+
+    let spans = subscriber.spans.lock().unwrap();
+    let has_span = |service: &str, phase: &str| spans.iter().any(|span|
+        span.get("name").map(String::as_str) == Some("service")
+        && span.get("service").map(String::as_str) == Some(service)
+        && span.get("phase").map(String::as_str) == Some(phase)
+    );
+
+    assert!(has_span(&background_service_name, "start"));
+    assert!(has_span(&background_service_name, "drain"));
+    assert!(has_span(&background_service_name, "stop"));
+    assert!(has_span(&service_name, "execute"));
+
+    let events = subscriber.events.lock().unwrap();
+    assert!(events.iter().any(|event|
+        event.get("message").map(String::as_str) == Some("Service started")
+        && event.get("service").map(String::as_str) == Some(background_service_name.as_str())
+    ));
+}
@@ -0,0 +1,60 @@
+use tokio::time::Instant;
+use std::{future::Future, sync::{Arc, RwLock}, time::Duration};
+
+use super::TokioClock;
+
+#[async_trait_with_sync::async_trait(Sync)]
+pub trait IClock: Sync + Send + 'static {
+    fn now(&self) -> Instant;
+    async fn sleep_until(&self, deadline: Instant);
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeoutExpired;
+
+/// Host timers (lifecycle timeouts, supervisor restart window). By default ```TokioClock```.
+/// Resolve as ```Arc<Clock>```
+pub struct Clock {
+    clock: RwLock<Arc<dyn IClock>>,
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self { clock: RwLock::new(Arc::new(TokioClock)) }
+    }
+}
+
+impl Clock {
+    /// Replace clock, timers already waiting keep previous clock
+    pub fn set_clock<TClock: IClock>(&self, clock: TClock) {
+        *self.clock.write().unwrap() = Arc::new(clock);
+    }
+
+    pub fn now(&self) -> Instant {
+        self.get_clock().now()
+    }
+
+    pub async fn sleep(&self, duration: Duration) {
+        self.sleep_until(self.now() + duration).await
+    }
+
+    pub async fn sleep_until(&self, deadline: Instant) {
+        self.get_clock().sleep_until(deadline).await
+    }
+
+    pub async fn timeout<TFuture: Future>(&self, duration: Duration, future: TFuture) -> Result<TFuture::Output, TimeoutExpired> {
+        self.timeout_at(self.now() + duration, future).await
+    }
+
+    pub async fn timeout_at<TFuture: Future>(&self, deadline: Instant, future: TFuture) -> Result<TFuture::Output, TimeoutExpired> {
+        tokio::select! {
+            biased;
+            output = future => Ok(output),
+            _ = self.sleep_until(deadline) => Err(TimeoutExpired),
+        }
+    }
+
+    fn get_clock(&self) -> Arc<dyn IClock> {
+        self.clock.read().unwrap().clone()
+    }
+}
@@ -0,0 +1,54 @@
+use tokio::{sync::watch, time::Instant};
+use std::{sync::Arc, time::Duration};
+
+use super::IClock;
+
+/// Time moves only on ```advance```. Clones share time
+#[derive(Clone)]
+pub struct ManualClock {
+    start: Instant,
+    elapsed_sender: Arc<watch::Sender<Duration>>,
+    elapsed_receiver: watch::Receiver<Duration>,
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        let (elapsed_sender, elapsed_receiver) = watch::channel(Duration::ZERO);
+
+        Self {
+            start: Instant::now(),
+            elapsed_sender: Arc::new(elapsed_sender),
+            elapsed_receiver,
+        }
+    }
+}
+
+impl ManualClock {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Move time forward and wake timers with reached deadline
+    pub fn advance(&self, duration: Duration) {
+        let elapsed = *self.elapsed_receiver.borrow() + duration;
+        // receiver stored in self, send can't fail
+        _ = self.elapsed_sender.send(elapsed);
+    }
+}
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IClock for ManualClock {
+    fn now(&self) -> Instant {
+        self.start + *self.elapsed_receiver.borrow()
+    }
+
+    async fn sleep_until(&self, deadline: Instant) {
+        let mut elapsed_receiver = self.elapsed_receiver.clone();
+
+        while self.start + *elapsed_receiver.borrow() < deadline {
+            if elapsed_receiver.changed().await.is_err() {
+                return;
+            }
+        }
+    }
+}
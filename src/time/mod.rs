@@ -0,0 +1,8 @@
+mod clock;
+pub use clock::*;
+
+mod tokio_clock;
+pub use tokio_clock::*;
+
+mod manual_clock;
+pub use manual_clock::*;
@@ -0,0 +1,17 @@
+use tokio::time::Instant;
+
+use super::IClock;
+
+/// Tokio timers, follow paused time in tests (```tokio::time::pause```)
+pub struct TokioClock;
+
+#[async_trait_with_sync::async_trait(Sync)]
+impl IClock for TokioClock {
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+
+    async fn sleep_until(&self, deadline: Instant) {
+        tokio::time::sleep_until(deadline).await
+    }
+}
@@ -0,0 +1,48 @@
+use std::future::Future;
+
+use anthill_di::types::TypeInfo;
+
+/// Lifecycle phase recorded in service span
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub (crate) enum ServicePhase {
+    Start,
+    Drain,
+    Stop,
+    Execute,
+}
+
+impl ServicePhase {
+    #[cfg_attr(not(feature = "tracing"), allow(dead_code))]
+    pub (crate) fn as_str(&self) -> &'static str {
+        match self {
+            ServicePhase::Start => "start",
+            ServicePhase::Drain => "drain",
+            ServicePhase::Stop => "stop",
+            ServicePhase::Execute => "execute",
+        }
+    }
+}
+
+/// Run future inside ```service``` span with service type name and phase, no-op without ```tracing``` feature
+#[cfg(feature = "tracing")]
+pub (crate) fn in_service_span<F: Future>(future: F, type_info: &TypeInfo, phase: ServicePhase) -> impl Future<Output = F::Output> {
+    use tracing::Instrument;
+
+    future.instrument(tracing::info_span!("service", service = %type_info.type_name, phase = phase.as_str()))
+}
+
+/// Run future inside ```service``` span with service type name and phase, no-op without ```tracing``` feature
+#[cfg(not(feature = "tracing"))]
+pub (crate) fn in_service_span<F: Future>(future: F, _type_info: &TypeInfo, _phase: ServicePhase) -> impl Future<Output = F::Output> {
+    future
+}
+
+/// Host event with structured fields, emitted to ```tracing``` when feature enabled, otherwise to ```log``` as ```message [field=value]```
+macro_rules! host_event {
+    ($level:ident, $message:literal $(, $field:ident = $value:expr)* $(,)?) => {{
+        #[cfg(feature = "tracing")]
+        tracing::$level!($($field = ?$value,)* $message);
+        #[cfg(not(feature = "tracing"))]
+        log::$level!(concat!($message $(, " [", stringify!($field), "={:?}]")*) $(, $value)*);
+    }};
+}
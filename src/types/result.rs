@@ -3,12 +3,20 @@ use anthill_di::types::{AddDependencyError, BuildDependencyError, TypeInfo, MapC
 use thiserror::Error;
 use anthill_di_configuration_extension::types::LoadConfigurationError;
 
+use crate::services::{ServiceFailure, ServiceId, ServiceState};
+
 #[derive(Error, Debug)]
 pub enum AppRunError {
     #[error("Service start timeout expired: [{timeout_duration:?}] [{service_type_info:?}]")]
     ServiceStartTimeoutExpired { timeout_duration: Duration, service_type_info: TypeInfo, },
     #[error("Service end timeout expired: [{timeout_duration:?}] [{service_type_info:?}]")]
     ServiceStopTimeoutExpired { timeout_duration: Duration, service_type_info: TypeInfo, },
+    #[error("Service start failed: [{service_type_info:?}] [{message}]")]
+    ServiceStartFailed { service_type_info: TypeInfo, message: String, },
+    #[error("Service stop failed: [{service_type_info:?}] [{message}]")]
+    ServiceStopFailed { service_type_info: TypeInfo, message: String, },
+    #[error("Service failure escalated: [{0:?}]")]
+    ServiceFailed(ServiceFailure),
     #[error("Ioc add dependency error: [{0:?}]")]
     IocAddDependencyError(AddDependencyError),
     #[error("Ioc build dependency error: [{0:?}]")]
@@ -17,6 +25,26 @@ pub enum AppRunError {
     IocMapComponentError(MapComponentError),
     #[error("Load configuration error: [{0:?}]")]
     LoadConfigurationError(LoadConfigurationError),
+    #[error("Service manager error: [{0:?}]")]
+    ServiceManagerError(ServiceManagerError),
+    #[error("Startup failed: [{startup_type_info:?}] [{err:?}]")]
+    StartupFailed { startup_type_info: TypeInfo, err: StartupError, },
+    #[error("Startup dependency not found: [{startup_type_info:?}] [{dependency_type_info:?}]")]
+    StartupDependencyNotFound { startup_type_info: TypeInfo, dependency_type_info: TypeInfo, },
+    #[error("Startup cyclic dependency: [{startup_type_info:?}]")]
+    StartupCyclicDependency { startup_type_info: TypeInfo, },
+    #[error("Pre start hook failed: [{hook_type_info:?}] [{err:?}]")]
+    PreStartHookFailed { hook_type_info: TypeInfo, err: HookError, },
+    #[error("Pre start hook timeout expired: [{timeout_duration:?}] [{hook_type_info:?}]")]
+    PreStartHookTimeoutExpired { timeout_duration: Duration, hook_type_info: TypeInfo, },
+    #[error("Post stop hook failed: [{hook_type_info:?}] [{err:?}]")]
+    PostStopHookFailed { hook_type_info: TypeInfo, err: HookError, },
+    #[error("Post stop hook timeout expired: [{timeout_duration:?}] [{hook_type_info:?}]")]
+    PostStopHookTimeoutExpired { timeout_duration: Duration, hook_type_info: TypeInfo, },
+    #[error("Application already running: [{pid_file}] [{pid:?}]")]
+    AlreadyRunning { pid_file: String, pid: Option<u32>, },
+    #[error("Pid file error: [{pid_file}] [{message}]")]
+    PidFileError { pid_file: String, message: String, },
 }
 
 impl PartialEq for AppRunError {
@@ -24,10 +52,23 @@ impl PartialEq for AppRunError {
         match (self, other) {
             (Self::ServiceStartTimeoutExpired { timeout_duration: l_timeout_duration, service_type_info: l_service_type_info }, Self::ServiceStartTimeoutExpired { timeout_duration: r_timeout_duration, service_type_info: r_service_type_info }) => l_timeout_duration == r_timeout_duration && l_service_type_info == r_service_type_info,
             (Self::ServiceStopTimeoutExpired { timeout_duration: l_timeout_duration, service_type_info: l_service_type_info }, Self::ServiceStopTimeoutExpired { timeout_duration: r_timeout_duration, service_type_info: r_service_type_info }) => l_timeout_duration == r_timeout_duration && l_service_type_info == r_service_type_info,
+            (Self::ServiceStartFailed { service_type_info: l_service_type_info, .. }, Self::ServiceStartFailed { service_type_info: r_service_type_info, .. }) => l_service_type_info == r_service_type_info,
+            (Self::ServiceStopFailed { service_type_info: l_service_type_info, .. }, Self::ServiceStopFailed { service_type_info: r_service_type_info, .. }) => l_service_type_info == r_service_type_info,
+            (Self::ServiceFailed(l0), Self::ServiceFailed(r0)) => l0 == r0,
             (Self::IocAddDependencyError(l0), Self::IocAddDependencyError(r0)) => l0 == r0,
             (Self::IocBuildDependencyError(l0), Self::IocBuildDependencyError(r0)) => l0 == r0,
             (Self::IocMapComponentError(l0), Self::IocMapComponentError(r0)) => l0 == r0,
             (Self::LoadConfigurationError(..), Self::LoadConfigurationError(..)) => true,
+            (Self::ServiceManagerError(l0), Self::ServiceManagerError(r0)) => l0 == r0,
+            (Self::StartupFailed { startup_type_info: l_startup_type_info, err: l_err }, Self::StartupFailed { startup_type_info: r_startup_type_info, err: r_err }) => l_startup_type_info == r_startup_type_info && l_err == r_err,
+            (Self::StartupDependencyNotFound { startup_type_info: l_startup_type_info, dependency_type_info: l_dependency_type_info }, Self::StartupDependencyNotFound { startup_type_info: r_startup_type_info, dependency_type_info: r_dependency_type_info }) => l_startup_type_info == r_startup_type_info && l_dependency_type_info == r_dependency_type_info,
+            (Self::StartupCyclicDependency { startup_type_info: l_startup_type_info }, Self::StartupCyclicDependency { startup_type_info: r_startup_type_info }) => l_startup_type_info == r_startup_type_info,
+            (Self::PreStartHookFailed { hook_type_info: l_hook_type_info, err: l_err }, Self::PreStartHookFailed { hook_type_info: r_hook_type_info, err: r_err }) => l_hook_type_info == r_hook_type_info && l_err == r_err,
+            (Self::PreStartHookTimeoutExpired { timeout_duration: l_timeout_duration, hook_type_info: l_hook_type_info }, Self::PreStartHookTimeoutExpired { timeout_duration: r_timeout_duration, hook_type_info: r_hook_type_info }) => l_timeout_duration == r_timeout_duration && l_hook_type_info == r_hook_type_info,
+            (Self::PostStopHookFailed { hook_type_info: l_hook_type_info, err: l_err }, Self::PostStopHookFailed { hook_type_info: r_hook_type_info, err: r_err }) => l_hook_type_info == r_hook_type_info && l_err == r_err,
+            (Self::PostStopHookTimeoutExpired { timeout_duration: l_timeout_duration, hook_type_info: l_hook_type_info }, Self::PostStopHookTimeoutExpired { timeout_duration: r_timeout_duration, hook_type_info: r_hook_type_info }) => l_timeout_duration == r_timeout_duration && l_hook_type_info == r_hook_type_info,
+            (Self::AlreadyRunning { pid_file: l_pid_file, pid: l_pid }, Self::AlreadyRunning { pid_file: r_pid_file, pid: r_pid }) => l_pid_file == r_pid_file && l_pid == r_pid,
+            (Self::PidFileError { pid_file: l_pid_file, message: l_message }, Self::PidFileError { pid_file: r_pid_file, message: r_message }) => l_pid_file == r_pid_file && l_message == r_message,
             _ => false,
         }
     }
@@ -35,6 +76,16 @@ impl PartialEq for AppRunError {
 
 pub type AppRunResult = Result<(), AppRunError>;
 
+#[derive(Error, Debug, PartialEq)]
+pub enum PidFileError {
+    #[error("Pid file locked by running instance: [{pid_file}] [{pid:?}]")]
+    AlreadyLocked { pid_file: String, pid: Option<u32>, },
+    #[error("Pid file io error: [{pid_file}] [{message}]")]
+    IoError { pid_file: String, message: String, },
+}
+
+pub type PidFileResult<T> = Result<T, PidFileError>;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum AddServiceError {
     #[error("Ioc add dependency error: [{0:?}]")]
@@ -59,6 +110,24 @@ pub enum AddStartupError {
 
 pub type AddStartupResult = Result<(), AddStartupError>;
 
+#[derive(Error, Debug, PartialEq)]
+pub enum StartupError {
+    #[error("Ioc add dependency error: [{0:?}]")]
+    IocAddDependencyError(AddDependencyError),
+    #[error("Ioc build dependency error: [{0:?}]")]
+    IocBuildDependencyError(BuildDependencyError),
+    #[error("Ioc map dependency error: [{0:?}]")]
+    IocMapComponentError(MapComponentError),
+    #[error("Add service error: [{0:?}]")]
+    AddServiceError(AddServiceError),
+    #[error("Add life time manager error: [{0:?}]")]
+    AddLifeTimeManagerError(AddLifeTimeManagerError),
+    #[error("Startup error: [{message}]")]
+    Custom { message: String, },
+}
+
+pub type StartupResult = Result<(), StartupError>;
+
 #[derive(Error, Debug, PartialEq)]
 pub enum AddLifeTimeManagerError {
     #[error("Ioc add dependency error: [{0:?}]")]
@@ -71,6 +140,54 @@ pub enum AddLifeTimeManagerError {
 
 pub type AddLifeTimeManagerResult = Result<(), AddLifeTimeManagerError>;
 
+#[derive(Error, Debug, PartialEq)]
+pub enum AddHookError {
+    #[error("Ioc map dependency error: [{0:?}]")]
+    IocMapComponentError(MapComponentError),
+}
+
+pub type AddHookResult = Result<(), AddHookError>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AddConfigurationError {
+    #[error("Ioc add dependency error: [{0:?}]")]
+    IocAddDependencyError(AddDependencyError),
+}
+
+pub type AddConfigurationResult = Result<(), AddConfigurationError>;
+
+#[derive(Error, Debug)]
+pub enum ReloadConfigurationError {
+    #[error("Ioc build dependency error: [{0:?}]")]
+    IocBuildDependencyError(BuildDependencyError),
+    #[error("Load configuration error: [{0:?}]")]
+    LoadConfigurationError(LoadConfigurationError),
+}
+
+impl PartialEq for ReloadConfigurationError {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::IocBuildDependencyError(l0), Self::IocBuildDependencyError(r0)) => l0 == r0,
+            (Self::LoadConfigurationError(..), Self::LoadConfigurationError(..)) => true,
+            _ => false,
+        }
+    }
+}
+
+pub type ReloadConfigurationResult = Result<(), ReloadConfigurationError>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum HookError {
+    #[error("Ioc build dependency error: [{0:?}]")]
+    IocBuildDependencyError(BuildDependencyError),
+    #[error("Hook panic: [{message}]")]
+    Panic { message: String, },
+    #[error("Hook error: [{message}]")]
+    Custom { message: String, },
+}
+
+pub type HookResult = Result<(), HookError>;
+
 #[derive(Error, Debug)]
 pub enum RegisterDefaultConfigurationError {
     #[error("Ioc add dependency error: [{0:?}]")]
@@ -80,4 +197,72 @@ pub enum RegisterDefaultConfigurationError {
     IoError(std::io::Error),
 }
 
-pub type RegisterDefaultConfigurationResult = Result<(), RegisterDefaultConfigurationError>;
\ No newline at end of file
+pub type RegisterDefaultConfigurationResult = Result<(), RegisterDefaultConfigurationError>;
+
+#[derive(Error, Debug)]
+pub enum RunBlockingError {
+    #[error("Load configuration error: [{0:?}]")]
+    LoadConfigurationError(LoadConfigurationError),
+    #[error("Runtime build error: [{0:?}]")]
+    RuntimeBuildError(std::io::Error),
+    #[error("Ioc build dependency error: [{0:?}]")]
+    IocBuildDependencyError(BuildDependencyError),
+    #[error("Add startup error: [{0:?}]")]
+    AddStartupError(AddStartupError),
+    #[error("App run error: [{0:?}]")]
+    AppRunError(Box<AppRunError>),
+    #[error("Auto registration error: [{0:?}]")]
+    AutoRegistrationError(AutoRegistrationError),
+}
+
+pub type RunBlockingResult = Result<(), RunBlockingError>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum AutoRegistrationError {
+    #[error("Add service error: [{0:?}]")]
+    AddServiceError(AddServiceError),
+    #[error("Add startup error: [{0:?}]")]
+    AddStartupError(AddStartupError),
+}
+
+pub type AutoRegistrationResult = Result<(), AutoRegistrationError>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum ServiceManagerError {
+    #[error("Service not found: [{service_id:?}]")]
+    ServiceNotFound { service_id: ServiceId, },
+    #[error("Invalid service state: [{service_id:?}] [{state:?}]")]
+    InvalidServiceState { service_id: ServiceId, state: ServiceState, },
+    #[error("Service start timeout expired: [{timeout_duration:?}] [{service_type_info:?}]")]
+    ServiceStartTimeoutExpired { timeout_duration: Duration, service_type_info: TypeInfo, },
+    #[error("Service end timeout expired: [{timeout_duration:?}] [{service_type_info:?}]")]
+    ServiceStopTimeoutExpired { timeout_duration: Duration, service_type_info: TypeInfo, },
+    #[error("Service start failed: [{service_type_info:?}] [{message}]")]
+    ServiceStartFailed { service_type_info: TypeInfo, message: String, },
+    #[error("Service stop failed: [{service_type_info:?}] [{message}]")]
+    ServiceStopFailed { service_type_info: TypeInfo, message: String, },
+    #[error("Ioc build dependency error: [{0:?}]")]
+    IocBuildDependencyError(BuildDependencyError),
+}
+
+pub type ServiceManagerResult<T = ()> = Result<T, ServiceManagerError>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum TaskSpawnerError {
+    #[error("Task spawner not bound to service: [{task}]")]
+    NotBound { task: String, },
+    #[error("Task spawner owner not found: [{service_id:?}] [{task}]")]
+    OwnerNotFound { service_id: ServiceId, task: String, },
+}
+
+pub type TaskSpawnerResult<T> = Result<T, TaskSpawnerError>;
+
+#[derive(Error, Debug, PartialEq)]
+pub enum SocketActivationError {
+    #[error("Activated socket not found: [{name}]")]
+    SocketNotFound { name: String, },
+    #[error("Activated socket has unexpected type: [{name}] [{expected}]")]
+    UnexpectedSocketType { name: String, expected: String, },
+}
+
+pub type SocketActivationResult<T> = Result<T, SocketActivationError>;